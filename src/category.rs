@@ -0,0 +1,474 @@
+use std::cmp::Reverse;
+use std::collections::BTreeMap;
+use std::fmt;
+use std::fs;
+use std::io;
+use std::path::Path;
+use std::sync::OnceLock;
+
+use globset::{GlobBuilder, GlobSet, GlobSetBuilder};
+use regex::{RegexSet, RegexSetBuilder};
+use serde::Deserialize;
+
+use crate::i18n::{fill, Locale};
+use crate::winpath::{KnownFolder, WinPath};
+
+/// 内置分类规则（即原先硬编码的判断链）
+pub const DEFAULT_CATEGORY_RULES: &str = include_str!("default_categories.toml");
+
+/// 未命中任何规则、且规则文件未指定 `default_id` 时的默认分类 ID
+pub const DEFAULT_CATEGORY_ID: &str = "other";
+
+/// 按路径特征将扫描目标归类（使用内置规则），返回分类 ID
+pub fn categorize_target(file_path: &str) -> &'static str {
+    CategoryRules::builtin().categorize(file_path)
+}
+
+/// 分类规则文件加载错误
+#[derive(Debug)]
+pub enum CategoryError {
+    Io(io::Error),
+    Toml(toml::de::Error),
+    /// 分类 `label`（ID）中的某个通配符或正则无效
+    Pattern { label: String, message: String },
+    /// 分类 `label`（ID）没有任何匹配条件
+    EmptyRule(String),
+    /// 分类 `label`（ID）引用了未知的常用目录名
+    UnknownFolder { label: String, name: String },
+}
+
+impl CategoryError {
+    /// 按 `locale` 生成错误信息
+    pub fn localized(&self, locale: Locale) -> String {
+        let m = locale.messages();
+        match self {
+            CategoryError::Io(e) => fill(m.error_categories_io, &[e]),
+            CategoryError::Toml(e) => fill(m.error_categories_toml, &[e]),
+            CategoryError::Pattern { label, message } => {
+                fill(m.error_category_pattern, &[label, message])
+            }
+            CategoryError::EmptyRule(label) => fill(m.error_category_empty, &[label]),
+            CategoryError::UnknownFolder { label, name } => {
+                let known: Vec<_> = KnownFolder::ALL.iter().map(|k| k.name()).collect();
+                fill(m.error_category_folder, &[label, name, &known.join(", ")])
+            }
+        }
+    }
+}
+
+impl fmt::Display for CategoryError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.localized(Locale::default()))
+    }
+}
+
+impl std::error::Error for CategoryError {}
+
+impl From<io::Error> for CategoryError {
+    fn from(e: io::Error) -> Self {
+        CategoryError::Io(e)
+    }
+}
+
+impl From<toml::de::Error> for CategoryError {
+    fn from(e: toml::de::Error) -> Self {
+        CategoryError::Toml(e)
+    }
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleFile {
+    #[serde(default)]
+    default_id: Option<String>,
+    default_label: LabelSpec,
+    #[serde(default, rename = "category")]
+    categories: Vec<RuleSpec>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct RuleSpec {
+    #[serde(default)]
+    id: Option<String>,
+    label: LabelSpec,
+    #[serde(default)]
+    priority: i32,
+    #[serde(default)]
+    prefix: Vec<String>,
+    #[serde(default)]
+    glob: Vec<String>,
+    #[serde(default)]
+    regex: Vec<String>,
+    #[serde(default)]
+    segment: Vec<String>,
+    #[serde(default)]
+    segment_prefix: Vec<String>,
+    #[serde(default)]
+    segments: Vec<String>,
+    #[serde(default)]
+    known_folder: Vec<String>,
+    #[serde(default)]
+    extension: Vec<String>,
+}
+
+/// 分类显示名：单一名称，或以语言代码为键的多语言名称
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum LabelSpec {
+    Plain(String),
+    Localized(BTreeMap<String, String>),
+}
+
+/// 编译后的分类显示名
+#[derive(Debug)]
+struct Label {
+    /// 无对应语言时使用的名称
+    fallback: String,
+    localized: Vec<(Locale, String)>,
+}
+
+impl Label {
+    fn compile(spec: LabelSpec) -> Self {
+        match spec {
+            LabelSpec::Plain(name) => Label {
+                fallback: name,
+                localized: Vec::new(),
+            },
+            LabelSpec::Localized(names) => {
+                // 不认识的语言代码忽略，便于旧版本读取含新语言的规则文件
+                let localized: Vec<_> = names
+                    .iter()
+                    .filter_map(|(code, name)| Some((Locale::from_code(code)?, name.clone())))
+                    .collect();
+                let fallback = localized
+                    .iter()
+                    .find(|(locale, _)| *locale == Locale::default())
+                    .map(|(_, name)| name.clone())
+                    .or_else(|| names.into_values().next())
+                    .unwrap_or_default();
+                Label { fallback, localized }
+            }
+        }
+    }
+
+    fn get(&self, locale: Locale) -> &str {
+        self.localized
+            .iter()
+            .find(|(l, _)| *l == locale)
+            .map_or(self.fallback.as_str(), |(_, name)| name.as_str())
+    }
+}
+
+impl RuleSpec {
+    fn is_empty(&self) -> bool {
+        self.prefix.is_empty()
+            && self.glob.is_empty()
+            && self.regex.is_empty()
+            && self.segment.is_empty()
+            && self.segment_prefix.is_empty()
+            && self.segments.is_empty()
+            && self.known_folder.is_empty()
+            && self.extension.is_empty()
+    }
+}
+
+/// 编译后的一条分类规则
+#[derive(Debug)]
+struct CategoryRule {
+    id: String,
+    label: Label,
+    priority: i32,
+    prefixes: Vec<WinPath>,
+    globs: GlobSet,
+    regexes: RegexSet,
+    segments: Vec<String>,
+    segment_prefixes: Vec<String>,
+    sequences: Vec<Vec<String>>,
+    known_folders: Vec<KnownFolder>,
+    extensions: Vec<String>,
+}
+
+impl CategoryRule {
+    fn compile(spec: RuleSpec) -> Result<Self, CategoryError> {
+        let is_empty = spec.is_empty();
+        let label = Label::compile(spec.label);
+        // 未指定 ID 时以默认语言的显示名作为 ID
+        let id = spec.id.unwrap_or_else(|| label.fallback.clone());
+        if is_empty {
+            return Err(CategoryError::EmptyRule(id));
+        }
+        let pattern_error = |message: String| CategoryError::Pattern {
+            label: id.clone(),
+            message,
+        };
+
+        let mut globs = GlobSetBuilder::new();
+        for pattern in &spec.glob {
+            let glob = GlobBuilder::new(&normalize_separators(pattern))
+                .case_insensitive(true)
+                .backslash_escape(false)
+                .build()
+                .map_err(|e| pattern_error(e.to_string()))?;
+            globs.add(glob);
+        }
+        let globs = globs.build().map_err(|e| pattern_error(e.to_string()))?;
+
+        let regexes = RegexSetBuilder::new(&spec.regex)
+            .case_insensitive(true)
+            .build()
+            .map_err(|e| pattern_error(e.to_string()))?;
+
+        let known_folders = spec
+            .known_folder
+            .iter()
+            .map(|name| {
+                KnownFolder::from_name(name).ok_or_else(|| CategoryError::UnknownFolder {
+                    label: id.clone(),
+                    name: name.clone(),
+                })
+            })
+            .collect::<Result<Vec<_>, _>>()?;
+
+        Ok(CategoryRule {
+            prefixes: spec.prefix.iter().map(|p| WinPath::parse(p)).collect(),
+            sequences: spec
+                .segments
+                .iter()
+                .map(|s| WinPath::parse(s).components)
+                .filter(|s| !s.is_empty())
+                .collect(),
+            extensions: spec
+                .extension
+                .iter()
+                .map(|e| e.trim_start_matches('.').to_lowercase())
+                .collect(),
+            segments: spec.segment,
+            segment_prefixes: spec.segment_prefix,
+            known_folders,
+            id,
+            label,
+            priority: spec.priority,
+            globs,
+            regexes,
+        })
+    }
+
+    fn matches(&self, raw: &str, path: &WinPath) -> bool {
+        self.prefixes.iter().any(|p| path.starts_with(p))
+            || self.segments.iter().any(|s| path.has_component(s))
+            || self.segment_prefixes.iter().any(|s| path.has_component_prefix(s))
+            || self.sequences.iter().any(|s| path.has_sequence(s))
+            || (!self.known_folders.is_empty()
+                && path.known_folders().iter().any(|k| self.known_folders.contains(k)))
+            || (!self.extensions.is_empty()
+                && path.extension().is_some_and(|e| self.extensions.contains(&e)))
+            || self.globs.is_match(raw)
+            || self.regexes.is_match(raw)
+    }
+}
+
+/// 一组按优先级排列的扫描目标分类规则
+#[derive(Debug)]
+pub struct CategoryRules {
+    rules: Vec<CategoryRule>,
+    default_id: String,
+    default_label: Label,
+}
+
+impl CategoryRules {
+    /// 内置默认规则
+    pub fn builtin() -> &'static CategoryRules {
+        static BUILTIN: OnceLock<CategoryRules> = OnceLock::new();
+        BUILTIN.get_or_init(|| {
+            CategoryRules::from_toml(DEFAULT_CATEGORY_RULES).expect("内置分类规则应当有效")
+        })
+    }
+
+    /// 从 TOML 文本解析规则
+    pub fn from_toml(text: &str) -> Result<Self, CategoryError> {
+        let file: RuleFile = toml::from_str(text)?;
+        let mut rules = file
+            .categories
+            .into_iter()
+            .map(CategoryRule::compile)
+            .collect::<Result<Vec<_>, _>>()?;
+        // 稳定排序：优先级相同时保持书写顺序
+        rules.sort_by_key(|rule| Reverse(rule.priority));
+        Ok(CategoryRules {
+            rules,
+            default_id: file.default_id.unwrap_or_else(|| DEFAULT_CATEGORY_ID.to_string()),
+            default_label: Label::compile(file.default_label),
+        })
+    }
+
+    /// 从 TOML 文件加载规则
+    pub fn load(path: &Path) -> Result<Self, CategoryError> {
+        Self::from_toml(&fs::read_to_string(path)?)
+    }
+
+    /// 返回目标路径所属分类的 ID（与显示语言无关，可用于跨语言比较导出结果）
+    pub fn categorize(&self, file_path: &str) -> &str {
+        let raw = normalize_separators(file_path);
+        let path = WinPath::parse(file_path);
+        self.rules
+            .iter()
+            .find(|rule| rule.matches(&raw, &path))
+            .map_or(self.default_id.as_str(), |rule| rule.id.as_str())
+    }
+
+    /// 分类 ID 在 `locale` 下的显示名，未知 ID 返回 `None`
+    pub fn label(&self, id: &str, locale: Locale) -> Option<&str> {
+        if id == self.default_id {
+            return Some(self.default_label.get(locale));
+        }
+        self.rules
+            .iter()
+            .find(|rule| rule.id == id)
+            .map(|rule| rule.label.get(locale))
+    }
+
+    /// 按匹配顺序列出全部分类的 ID 与 `locale` 下的显示名（含默认分类）
+    pub fn labels(&self, locale: Locale) -> Vec<(&str, &str)> {
+        let mut labels: Vec<_> = self
+            .rules
+            .iter()
+            .map(|r| (r.id.as_str(), r.label.get(locale)))
+            .collect();
+        labels.push((&self.default_id, self.default_label.get(locale)));
+        labels
+    }
+}
+
+fn normalize_separators(s: &str) -> String {
+    s.replace('/', "\\")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn rules(categories: &str) -> CategoryRules {
+        let text = format!("default_label = \"其他\"\n{}", categories);
+        CategoryRules::from_toml(&text).unwrap()
+    }
+
+    fn error(categories: &str) -> CategoryError {
+        let text = format!("default_label = \"其他\"\n{}", categories);
+        CategoryRules::from_toml(&text).unwrap_err()
+    }
+
+    #[test]
+    fn higher_priority_wins_and_ties_keep_file_order() {
+        let rules = rules(
+            r#"
+            [[category]]
+            id = "first"
+            label = "first"
+            segment = ["games"]
+
+            [[category]]
+            id = "second"
+            label = "second"
+            segment = ["games"]
+
+            [[category]]
+            id = "urgent"
+            label = "urgent"
+            priority = 10
+            extension = ["pak"]
+            "#,
+        );
+        assert_eq!(rules.categorize(r"D:\Games\a.pak"), "urgent");
+        assert_eq!(rules.categorize(r"D:\Games\a.exe"), "first");
+        assert_eq!(rules.categorize(r"D:\Tools\a.exe"), DEFAULT_CATEGORY_ID);
+        let ids: Vec<_> = rules.labels(Locale::En).into_iter().map(|(id, _)| id).collect();
+        assert_eq!(ids, ["urgent", "first", "second", DEFAULT_CATEGORY_ID]);
+    }
+
+    #[test]
+    fn supports_each_matcher_kind() {
+        let rules = rules(
+            r#"
+            [[category]]
+            id = "prefix"
+            label = "prefix"
+            prefix = ['C:\Program Files']
+
+            [[category]]
+            id = "glob"
+            label = "glob"
+            glob = ['**\Saved\*.log']
+
+            [[category]]
+            id = "regex"
+            label = "regex"
+            regex = ['\\pak\d+\\']
+
+            [[category]]
+            id = "segment"
+            label = "segment"
+            segment = ["steamapps"]
+            "#,
+        );
+        assert_eq!(rules.categorize(r"c:\program files\a.exe"), "prefix");
+        // 前缀按整段分量比较
+        assert_eq!(rules.categorize(r"C:\Program Files (x86)\a.exe"), DEFAULT_CATEGORY_ID);
+        assert_eq!(rules.categorize(r"D:\Game\Saved\CRASH.LOG"), "glob");
+        assert_eq!(rules.categorize("D:/Game/Saved/a.log"), "glob");
+        assert_eq!(rules.categorize(r"D:\Game\PAK12\a.bin"), "regex");
+        assert_eq!(rules.categorize(r"D:\SteamApps\common\a.exe"), "segment");
+        assert_eq!(rules.categorize(r"D:\steamapps2\a.exe"), DEFAULT_CATEGORY_ID);
+    }
+
+    #[test]
+    fn rejects_unknown_fields() {
+        assert!(matches!(
+            error("[[category]]\nlabel = \"x\"\nsegment = [\"a\"]\nprefixes = [\"C:\"]\n"),
+            CategoryError::Toml(_)
+        ));
+        assert!(matches!(error("colour = \"red\"\n"), CategoryError::Toml(_)));
+    }
+
+    #[test]
+    fn reports_invalid_rules_by_label() {
+        match error("[[category]]\nid = \"empty\"\nlabel = \"x\"\npriority = 3\n") {
+            CategoryError::EmptyRule(label) => assert_eq!(label, "empty"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        match error("[[category]]\nlabel = \"系统\"\nknown_folder = [\"system64\"]\n") {
+            CategoryError::UnknownFolder { label, name } => {
+                assert_eq!((label.as_str(), name.as_str()), ("系统", "system64"));
+            }
+            e => panic!("unexpected error: {:?}", e),
+        }
+        match error("[[category]]\nid = \"bad\"\nlabel = \"x\"\nregex = [\"(unclosed\"]\n") {
+            CategoryError::Pattern { label, .. } => assert_eq!(label, "bad"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+        match error("[[category]]\nid = \"bad_glob\"\nlabel = \"x\"\nglob = [\"a[\"]\n") {
+            CategoryError::Pattern { label, .. } => assert_eq!(label, "bad_glob"),
+            e => panic!("unexpected error: {:?}", e),
+        }
+    }
+
+    #[test]
+    fn builtin_anti_cheat_needs_a_whole_ace_segment() {
+        let rules = CategoryRules::builtin();
+        assert_ne!(rules.categorize(r"C:\Users\alice\Workspace\notes.txt"), "anti_cheat");
+        assert_ne!(rules.categorize(r"D:\Games\Peace\config.ini"), "anti_cheat");
+        assert_eq!(
+            rules.categorize(r"C:\Program Files\AntiCheatExpert\SGuard64.exe"),
+            "anti_cheat"
+        );
+        assert_eq!(rules.categorize(r"D:\Delta Force\ACE\ace-base.sys"), "anti_cheat");
+    }
+
+    #[test]
+    fn labels_follow_locale() {
+        let rules = CategoryRules::builtin();
+        assert_eq!(rules.label("anti_cheat", Locale::ZhCn), Some("反作弊组件"));
+        assert_eq!(rules.label("anti_cheat", Locale::En), Some("Anti-cheat components"));
+        assert_eq!(rules.label("no_such_id", Locale::En), None);
+    }
+}
@@ -0,0 +1,368 @@
+use std::path::PathBuf;
+
+use chrono::{NaiveDate, TimeDelta};
+use clap::{Args, Parser, Subcommand, ValueEnum};
+use fk_deltaforce::{
+    clamp_width, terminal_width, CategoryRules, HistoryFilter, Locale, PathNormalizer,
+    ReportOptions, RiskThresholds, VolumeMap, DEFAULT_WIDTH, HISTORY_DIR,
+};
+
+/// 火绒日志 ACE 反作弊扫盘行为分析工具
+/// ACE anti-cheat disk-scan analyzer for Huorong security logs
+#[derive(Debug, Parser)]
+#[command(
+    name = "fk-deltaforce",
+    version,
+    subcommand_precedence_over_arg = true,
+    after_help = EXIT_CODES_HELP
+)]
+pub struct Cli {
+    #[command(subcommand)]
+    pub command: Option<Command>,
+
+    /// 未指定子命令时等同于 `report`
+    #[command(flatten)]
+    pub report: ReportArgs,
+
+    #[command(flatten)]
+    pub global: GlobalArgs,
+}
+
+const EXIT_CODES_HELP: &str = "\
+退出码 / Exit codes:
+  0  成功 / Success
+  1  其他错误 / Other error
+  2  参数错误 / Invalid arguments
+  3  日志文件不存在 / Log file not found
+  4  不是火绒安全日志 / Not a Huorong security log
+  5  日志或历史库中没有 ACE 扫盘条目 / No ACE scan entries in the log or history
+  6  分类规则文件无效 / Invalid category rules file
+  7  JSON 统计快照无效 / Invalid JSON stats snapshot";
+
+/// 所有子命令共用的选项
+#[derive(Debug, Args)]
+pub struct GlobalArgs {
+    /// 导出文件的输出目录 / Directory for exported files
+    #[arg(short, long, global = true, value_name = "DIR", default_value = ".")]
+    pub output_dir: PathBuf,
+
+    /// 报告输出格式 / Report output format
+    #[arg(short, long, global = true, value_enum, default_value_t = OutputFormat::Text)]
+    pub format: OutputFormat,
+
+    /// 自定义分类规则 TOML 文件 / Custom category rules TOML file
+    #[arg(long, global = true, value_name = "FILE")]
+    pub categories: Option<PathBuf>,
+
+    /// 设备卷号到盘符的映射，如 `3=C,4=D` / Map `\Device\HarddiskVolumeN` to drive letters
+    #[arg(long, global = true, value_name = "N=DRIVE,...")]
+    pub volume_map: Option<VolumeMap>,
+
+    /// 保留 `C:\Users\<用户名>` 中的真实用户名 / Keep real user names in profile paths
+    #[arg(long, global = true)]
+    pub keep_user_names: bool,
+
+    /// 报告与提示语言，默认按 LC_ALL / LANG 环境变量或 Windows 区域设置 / Report language (zh-CN, en), defaults to the environment or Windows locale
+    #[arg(long, global = true, value_name = "LANG")]
+    pub lang: Option<Locale>,
+
+    /// 终端报告宽度（列数），默认跟随终端窗口 / Report width in columns (defaults to the terminal width)
+    #[arg(long, global = true, value_name = "COLUMNS")]
+    pub width: Option<usize>,
+
+    /// 历史库目录，默认为输出目录下的 `ace_history` / History store directory (defaults to `ace_history` in the output directory)
+    #[arg(long, global = true, value_name = "DIR")]
+    pub history_dir: Option<PathBuf>,
+
+    /// 将报告写入文件而不是标准输出 / Write the report to a file instead of stdout
+    #[arg(long, global = true, value_name = "FILE")]
+    pub report_file: Option<PathBuf>,
+
+    /// 安静模式：只输出报告本身，不显示进度提示 / Quiet mode: print the report only
+    #[arg(short, long, global = true)]
+    pub quiet: bool,
+
+    /// 结束时不等待按键（用于脚本/计划任务）/ Never wait for a key press before exiting
+    #[arg(long, global = true, conflicts_with = "pause")]
+    pub no_pause: bool,
+
+    /// 结束时总是等待按键 / Always wait for a key press before exiting
+    #[arg(long, global = true)]
+    pub pause: bool,
+}
+
+impl GlobalArgs {
+    /// 报告语言：`--lang` 优先，否则按环境变量与系统区域设置
+    pub fn locale(&self) -> Locale {
+        self.lang.unwrap_or_else(Locale::from_env)
+    }
+
+    /// 补全报告宽度、语言与分类显示名
+    pub fn report_options(&self, options: ReportOptions, rules: &CategoryRules) -> ReportOptions {
+        ReportOptions {
+            width: self.report_width(),
+            locale: self.locale(),
+            ..options
+        }
+        .with_categories(rules)
+    }
+
+    /// 终端报告宽度：写入文件时使用固定默认宽度，否则跟随终端窗口
+    pub fn report_width(&self) -> usize {
+        match (self.width, &self.report_file) {
+            (Some(width), _) => clamp_width(width),
+            (None, Some(_)) => DEFAULT_WIDTH,
+            (None, None) => terminal_width(),
+        }
+    }
+
+    /// 历史库目录：`--history-dir` 优先，否则为输出目录下的 [`HISTORY_DIR`]
+    pub fn history_dir(&self) -> PathBuf {
+        self.history_dir
+            .clone()
+            .unwrap_or_else(|| self.output_dir.join(HISTORY_DIR))
+    }
+
+    pub fn normalizer(&self) -> PathNormalizer {
+        PathNormalizer::new()
+            .with_volumes(self.volume_map.clone().unwrap_or_default())
+            .collapse_user_names(!self.keep_user_names)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, ValueEnum)]
+pub enum OutputFormat {
+    /// 终端文本 / Terminal text
+    Text,
+    /// 带 schema 版本的 JSON / Versioned JSON
+    Json,
+    /// 离线单文件 HTML / Self-contained offline HTML
+    Html,
+    /// GitHub 风格 Markdown / GitHub-flavored Markdown
+    Markdown,
+}
+
+#[derive(Debug, Subcommand)]
+pub enum Command {
+    /// 生成完整分析报告并导出 CSV / Full analysis report plus CSV export
+    Report(ReportArgs),
+    /// 仅导出高频扫描目标 CSV / Export the high-frequency target CSV only
+    Export(ExportArgs),
+    /// 按时间间隔切分游戏会话 / Split scanning into game sessions
+    Sessions(SessionArgs),
+    /// 统计触犯的火绒规则 / Summarize triggered Huorong rules
+    Rules(InputArgs),
+    /// 输出内置分类规则，或测试路径的分类结果 / Print built-in category rules or classify paths
+    Categories(CategoriesArgs),
+    /// 对比两份日志或 JSON 快照的扫盘行为 / Compare scanning between two logs or JSON snapshots
+    Diff(DiffArgs),
+    /// 将日志导入本地历史库（自动去重）/ Import logs into the local history store (de-duplicated)
+    Ingest(IngestArgs),
+    /// 按日期范围或机器汇总历史库生成报告 / Report on the history store, optionally by date range or machine
+    History(HistoryArgs),
+}
+
+#[derive(Debug, Args)]
+pub struct InputArgs {
+    /// 火绒日志文件路径，多份导出相互重叠时自动去重 / Huorong log files; overlapping exports are de-duplicated
+    #[arg(value_name = "LOG", default_value = "fk-df.txt")]
+    pub logs: Vec<PathBuf>,
+}
+
+#[derive(Debug, Args)]
+pub struct ReportArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    #[command(flatten)]
+    pub display: DisplayArgs,
+
+    #[command(flatten)]
+    pub session: SessionGapArgs,
+
+    /// CSV 导出行数上限 / Maximum rows in the CSV export
+    #[arg(long, value_name = "N", default_value_t = 200)]
+    pub export_limit: usize,
+
+    /// 不导出 CSV / Skip the CSV export
+    #[arg(long)]
+    pub no_export: bool,
+}
+
+impl ReportArgs {
+    pub fn options(&self) -> ReportOptions {
+        ReportOptions {
+            export_limit: self.export_limit,
+            ..self.display.options()
+        }
+    }
+}
+
+/// 报告各节的显示数量与风险阈值
+#[derive(Debug, Args)]
+pub struct DisplayArgs {
+    /// 进程分析显示数量 / Number of processes to show
+    #[arg(long, value_name = "N", default_value_t = 5)]
+    pub top_processes: usize,
+
+    /// 高频目标显示数量 / Number of top scanned files to show
+    #[arg(long, value_name = "N", default_value_t = 15)]
+    pub top_files: usize,
+
+    /// 文件类型显示数量 / Number of file extensions to show
+    #[arg(long, value_name = "N", default_value_t = 8)]
+    pub top_extensions: usize,
+
+    /// 热点目录展开的层级数（盘符为第 1 层）/ Depth of the hot directory tree (drive is level 1)
+    #[arg(long, value_name = "N", default_value_t = 4)]
+    pub tree_depth: usize,
+
+    /// 热点目录每层展开的子目录数 / Subdirectories to expand per level of the hot directory tree
+    #[arg(long, value_name = "N", default_value_t = 3)]
+    pub top_dirs: usize,
+
+    #[command(flatten)]
+    pub thresholds: ThresholdArgs,
+}
+
+impl DisplayArgs {
+    pub fn options(&self) -> ReportOptions {
+        ReportOptions {
+            top_processes: self.top_processes,
+            top_files: self.top_files,
+            top_extensions: self.top_extensions,
+            tree_depth: self.tree_depth,
+            top_dirs: self.top_dirs,
+            process_risk: self.thresholds.process_risk,
+            file_risk: self.thresholds.file_risk,
+            category_risk: self.thresholds.category_risk,
+            ..ReportOptions::default()
+        }
+    }
+}
+
+/// 风险分级阈值，格式为 `高危,中危`
+#[derive(Debug, Args)]
+pub struct ThresholdArgs {
+    /// 进程风险阈值 / Process risk thresholds `HIGH,MEDIUM`
+    #[arg(long, value_name = "HIGH,MEDIUM", default_value = "500,200")]
+    pub process_risk: RiskThresholds,
+
+    /// 文件风险阈值 / File risk thresholds `HIGH,MEDIUM`
+    #[arg(long, value_name = "HIGH,MEDIUM", default_value = "30,10")]
+    pub file_risk: RiskThresholds,
+
+    /// 分类风险阈值 / Category risk thresholds `HIGH,MEDIUM`
+    #[arg(long, value_name = "HIGH,MEDIUM", default_value = "1000,300")]
+    pub category_risk: RiskThresholds,
+}
+
+#[derive(Debug, Args)]
+pub struct SessionGapArgs {
+    /// 会话切分的空闲间隔（分钟）/ Idle gap in minutes that separates sessions
+    #[arg(long, value_name = "MINUTES", default_value_t = 10)]
+    pub session_gap: u32,
+}
+
+impl SessionGapArgs {
+    pub fn gap(&self) -> TimeDelta {
+        TimeDelta::minutes(i64::from(self.session_gap))
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct ExportArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    /// CSV 导出行数上限 / Maximum rows in the CSV export
+    #[arg(long, value_name = "N", default_value_t = 200)]
+    pub limit: usize,
+
+    /// 文件风险阈值 / File risk thresholds `HIGH,MEDIUM`
+    #[arg(long, value_name = "HIGH,MEDIUM", default_value = "30,10")]
+    pub file_risk: RiskThresholds,
+}
+
+impl ExportArgs {
+    pub fn options(&self) -> ReportOptions {
+        ReportOptions {
+            export_limit: self.limit,
+            file_risk: self.file_risk,
+            ..ReportOptions::default()
+        }
+    }
+}
+
+#[derive(Debug, Args)]
+pub struct SessionArgs {
+    #[command(flatten)]
+    pub input: InputArgs,
+
+    #[command(flatten)]
+    pub session: SessionGapArgs,
+}
+
+#[derive(Debug, Args)]
+pub struct CategoriesArgs {
+    /// 待测试的目标路径；不指定时输出内置规则模板 / Paths to classify; prints the built-in rules if omitted
+    #[arg(value_name = "PATH")]
+    pub paths: Vec<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct DiffArgs {
+    /// 之前的火绒日志或 `-f json` 导出的报告 / Earlier log or `-f json` report
+    #[arg(value_name = "BEFORE")]
+    pub before: PathBuf,
+
+    /// 之后的火绒日志或 `-f json` 导出的报告 / Later log or `-f json` report
+    #[arg(value_name = "AFTER")]
+    pub after: PathBuf,
+
+    /// 每节显示的文件数 / Number of files to show per section
+    #[arg(long, value_name = "N", default_value_t = 15)]
+    pub top: usize,
+}
+
+#[derive(Debug, Args)]
+pub struct IngestArgs {
+    /// 要导入的火绒日志文件 / Huorong log files to import
+    #[arg(value_name = "LOG", required = true)]
+    pub logs: Vec<PathBuf>,
+
+    /// 日志所在机器名（不区分大小写），默认为本机名 / Machine the logs came from, case-insensitive (defaults to this computer's name)
+    #[arg(long, value_name = "NAME")]
+    pub machine: Option<String>,
+}
+
+#[derive(Debug, Args)]
+pub struct HistoryArgs {
+    #[command(flatten)]
+    pub display: DisplayArgs,
+
+    #[command(flatten)]
+    pub session: SessionGapArgs,
+
+    /// 起始日期（含），如 2024-05-01 / First day to include, e.g. 2024-05-01
+    #[arg(long, value_name = "DATE")]
+    pub since: Option<NaiveDate>,
+
+    /// 截止日期（含）/ Last day to include
+    #[arg(long, value_name = "DATE")]
+    pub until: Option<NaiveDate>,
+
+    /// 只统计该机器的日志 / Only include logs from this machine
+    #[arg(long, value_name = "NAME")]
+    pub machine: Option<String>,
+}
+
+impl HistoryArgs {
+    pub fn filter(&self) -> HistoryFilter {
+        HistoryFilter {
+            since: self.since,
+            until: self.until,
+            machine: self.machine.clone(),
+        }
+    }
+}
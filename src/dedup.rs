@@ -0,0 +1,117 @@
+use std::collections::HashMap;
+
+use crate::entry::{AceLogEntry, EntryKey};
+
+/// 多份日志重叠时的去重：同一身份的条目在后一份输入中只计入超出前面输入的部分
+///
+/// 火绒日志时间戳只精确到秒，同一份日志内身份相同的多条记录可能是真实的重复扫描，
+/// 因此按「每份输入内的出现次数」比较：某身份在之前的输入中最多出现过 n 次，
+/// 当前输入里该身份的前 n 条视为重复，其余照常计入。
+#[derive(Debug, Default)]
+pub struct EntryDeduplicator {
+    /// 已结束的输入中各身份的最大出现次数
+    seen: HashMap<EntryKey, usize>,
+    /// 当前输入中各身份的出现次数
+    current: HashMap<EntryKey, usize>,
+    /// 当前为最后一份输入，只需记录之前见过的身份
+    last_input: bool,
+    duplicates: usize,
+}
+
+impl EntryDeduplicator {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 结束当前输入，之后的条目与此前所有输入比较
+    pub fn next_input(&mut self) {
+        for (key, count) in self.current.drain() {
+            let seen = self.seen.entry(key).or_insert(0);
+            *seen = (*seen).max(count);
+        }
+    }
+
+    /// 标记当前为最后一份输入：之后不会再有输入与它比较，不在之前输入中的条目无需保留身份，
+    /// 只有一份输入时完全不做去重
+    pub fn last_input(&mut self) {
+        self.last_input = true;
+    }
+
+    /// 记录一条日志，返回它是否应计入统计（`false` 表示与之前的输入重复）
+    pub fn insert(&mut self, entry: &AceLogEntry) -> bool {
+        if self.last_input && self.seen.is_empty() {
+            return true;
+        }
+        let key = entry.identity();
+        let previous = self.seen.get(&key).copied().unwrap_or(0);
+        if self.last_input && previous == 0 {
+            return true;
+        }
+        let count = self.current.entry(key).or_insert(0);
+        *count += 1;
+        if *count <= previous {
+            self.duplicates += 1;
+            false
+        } else {
+            true
+        }
+    }
+
+    /// 已丢弃的重复条目数
+    pub fn duplicates(&self) -> usize {
+        self.duplicates
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SCAN: &str = "2024-05-01 12:00:00\n操作进程：C:\\Program Files\\ACE\\SGuard64.exe\n操作类型：读取文件\n操作文件：C:\\Windows\\a.dll\n操作结果：已阻止\n";
+    const OTHER: &str = "2024-05-01 12:00:01\n操作进程：C:\\Program Files\\ACE\\SGuard64.exe\n操作类型：读取文件\n操作文件：C:\\Windows\\b.dll\n操作结果：已阻止\n";
+
+    /// 依次导入各份输入，返回每份输入中计入统计的条目数
+    fn kept_per_input(inputs: &[&[&str]], mark_last: bool) -> (Vec<usize>, usize) {
+        let mut dedup = EntryDeduplicator::new();
+        let mut kept = Vec::new();
+        for (i, input) in inputs.iter().enumerate() {
+            if mark_last && i + 1 == inputs.len() {
+                dedup.last_input();
+            }
+            let count = input
+                .iter()
+                .filter(|text| dedup.insert(&AceLogEntry::parse(text)))
+                .count();
+            kept.push(count);
+            dedup.next_input();
+        }
+        (kept, dedup.duplicates())
+    }
+
+    #[test]
+    fn keeps_repeats_within_one_input() {
+        assert_eq!(kept_per_input(&[&[SCAN, SCAN, OTHER]], false), (vec![3], 0));
+        assert_eq!(kept_per_input(&[&[SCAN, SCAN, OTHER]], true), (vec![3], 0));
+    }
+
+    #[test]
+    fn drops_only_the_overlap_with_earlier_inputs() {
+        // 第一份出现 2 次、第二份出现 3 次：第二份只多计 1 次
+        let inputs: &[&[&str]] = &[&[SCAN, SCAN], &[SCAN, SCAN, SCAN, OTHER]];
+        assert_eq!(kept_per_input(inputs, false), (vec![2, 2], 2));
+        assert_eq!(kept_per_input(inputs, true), (vec![2, 2], 2));
+    }
+
+    #[test]
+    fn compares_against_the_largest_earlier_count() {
+        let inputs: &[&[&str]] = &[&[SCAN, SCAN], &[SCAN], &[SCAN, SCAN]];
+        assert_eq!(kept_per_input(inputs, false), (vec![2, 0, 0], 3));
+        assert_eq!(kept_per_input(inputs, true), (vec![2, 0, 0], 3));
+    }
+
+    #[test]
+    fn identical_inputs_are_fully_duplicated() {
+        let inputs: &[&[&str]] = &[&[SCAN, OTHER, SCAN], &[SCAN, OTHER, SCAN]];
+        assert_eq!(kept_per_input(inputs, true), (vec![3, 0], 3));
+    }
+}
@@ -0,0 +1,162 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::stats::AceScanStats;
+
+/// 某一项（文件、分类、进程、规则）在两次统计中的次数
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountChange {
+    pub key: String,
+    pub before: usize,
+    pub after: usize,
+}
+
+impl CountChange {
+    /// 次数变化量（之后 - 之前）
+    pub fn delta(&self) -> i64 {
+        self.after as i64 - self.before as i64
+    }
+
+    /// 仅在新统计中出现
+    pub fn is_added(&self) -> bool {
+        self.before == 0 && self.after > 0
+    }
+
+    /// 仅在旧统计中出现
+    pub fn is_removed(&self) -> bool {
+        self.before > 0 && self.after == 0
+    }
+}
+
+/// 两张计数表的逐项对比，按变化量绝对值降序（相同时按名称）排列
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct CountDiff {
+    pub entries: Vec<CountChange>,
+}
+
+impl CountDiff {
+    /// 对比两张计数表，两边次数相同的项不收录
+    pub fn between(before: &HashMap<String, usize>, after: &HashMap<String, usize>) -> Self {
+        let mut entries: Vec<CountChange> = before
+            .keys()
+            .chain(after.keys().filter(|k| !before.contains_key(*k)))
+            .map(|key| CountChange {
+                key: key.clone(),
+                before: before.get(key).copied().unwrap_or(0),
+                after: after.get(key).copied().unwrap_or(0),
+            })
+            .filter(|c| c.before != c.after)
+            .collect();
+        entries.sort_by(|a, b| {
+            b.delta()
+                .unsigned_abs()
+                .cmp(&a.delta().unsigned_abs())
+                .then(a.key.cmp(&b.key))
+        });
+        CountDiff { entries }
+    }
+
+    /// 新出现的项
+    pub fn added(&self) -> impl Iterator<Item = &CountChange> {
+        self.entries.iter().filter(|c| c.is_added())
+    }
+
+    /// 消失的项
+    pub fn removed(&self) -> impl Iterator<Item = &CountChange> {
+        self.entries.iter().filter(|c| c.is_removed())
+    }
+
+    /// 两边都出现但次数变化的项
+    pub fn changed(&self) -> impl Iterator<Item = &CountChange> {
+        self.entries.iter().filter(|c| !c.is_added() && !c.is_removed())
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.entries.is_empty()
+    }
+}
+
+/// 两次扫盘统计（如游戏更新前后）的对比结果
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct StatsDiff {
+    pub total_before: usize,
+    pub total_after: usize,
+    pub blocked_before: usize,
+    pub blocked_after: usize,
+    /// 拦截率（百分比）
+    pub block_rate_before: f64,
+    pub block_rate_after: f64,
+    pub unique_files_before: usize,
+    pub unique_files_after: usize,
+    pub files: CountDiff,
+    pub categories: CountDiff,
+    pub processes: CountDiff,
+    pub rules: CountDiff,
+}
+
+impl StatsDiff {
+    /// 对比 `before` 与 `after` 两次统计
+    pub fn between(before: &AceScanStats, after: &AceScanStats) -> Self {
+        StatsDiff {
+            total_before: before.total_attempts,
+            total_after: after.total_attempts,
+            blocked_before: before.blocked_attempts,
+            blocked_after: after.blocked_attempts,
+            block_rate_before: before.block_rate(),
+            block_rate_after: after.block_rate(),
+            unique_files_before: before.unique_files.len(),
+            unique_files_after: after.unique_files.len(),
+            files: CountDiff::between(&before.unique_files, &after.unique_files),
+            categories: CountDiff::between(&before.target_categories, &after.target_categories),
+            processes: CountDiff::between(&before.processes, &after.processes),
+            rules: CountDiff::between(&before.rules_triggered, &after.rules_triggered),
+        }
+    }
+
+    /// 拦截率变化（百分点）
+    pub fn block_rate_change(&self) -> f64 {
+        self.block_rate_after - self.block_rate_before
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn counts(items: &[(&str, usize)]) -> HashMap<String, usize> {
+        items.iter().map(|(k, v)| (k.to_string(), *v)).collect()
+    }
+
+    fn keys<'a>(changes: impl Iterator<Item = &'a CountChange>) -> Vec<&'a str> {
+        changes.map(|c| c.key.as_str()).collect()
+    }
+
+    #[test]
+    fn splits_added_removed_and_changed_entries() {
+        let before = counts(&[("kept", 3), ("gone", 2), ("grew", 1), ("shrank", 9)]);
+        let after = counts(&[("kept", 3), ("new", 4), ("grew", 6), ("shrank", 8)]);
+        let diff = CountDiff::between(&before, &after);
+        assert_eq!(keys(diff.added()), ["new"]);
+        assert_eq!(keys(diff.removed()), ["gone"]);
+        assert_eq!(keys(diff.changed()), ["grew", "shrank"]);
+        assert!(diff.entries.iter().all(|c| c.key != "kept"));
+    }
+
+    #[test]
+    fn sorts_by_absolute_change_then_name() {
+        let before = counts(&[("b", 5), ("a", 1), ("c", 0)]);
+        let after = counts(&[("b", 1), ("a", 5), ("c", 2), ("d", 10)]);
+        let diff = CountDiff::between(&before, &after);
+        assert_eq!(keys(diff.entries.iter()), ["d", "a", "b", "c"]);
+        let deltas: Vec<_> = diff.entries.iter().map(CountChange::delta).collect();
+        assert_eq!(deltas, [10, 4, -4, 2]);
+    }
+
+    #[test]
+    fn identical_tables_have_no_changes() {
+        let table = counts(&[("a", 1), ("b", 2)]);
+        assert!(CountDiff::between(&table, &table).is_empty());
+        assert!(CountDiff::between(&HashMap::new(), &HashMap::new()).is_empty());
+    }
+}
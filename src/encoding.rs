@@ -0,0 +1,121 @@
+use std::fmt;
+
+use encoding_rs::{Encoding, GB18030, UTF_16BE, UTF_16LE, UTF_8};
+
+/// 日志文件的文本编码
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TextEncoding {
+    Utf8,
+    Utf8Bom,
+    Utf16Le,
+    Utf16Be,
+    /// GBK 为 GB18030 的子集，统一按 GB18030 解码
+    Gb18030,
+}
+
+impl TextEncoding {
+    /// 用于界面显示的编码名
+    pub fn name(self) -> &'static str {
+        match self {
+            TextEncoding::Utf8 => "UTF-8",
+            TextEncoding::Utf8Bom => "UTF-8 (BOM)",
+            TextEncoding::Utf16Le => "UTF-16LE",
+            TextEncoding::Utf16Be => "UTF-16BE",
+            TextEncoding::Gb18030 => "GBK/GB18030",
+        }
+    }
+
+    /// 对应的 `encoding_rs` 解码器
+    pub fn encoding(self) -> &'static Encoding {
+        match self {
+            TextEncoding::Utf8 | TextEncoding::Utf8Bom => UTF_8,
+            TextEncoding::Utf16Le => UTF_16LE,
+            TextEncoding::Utf16Be => UTF_16BE,
+            TextEncoding::Gb18030 => GB18030,
+        }
+    }
+}
+
+impl fmt::Display for TextEncoding {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.name())
+    }
+}
+
+/// 根据 BOM 和字节特征推断文本编码（`head` 为文件开头的若干字节）
+pub fn detect_encoding(head: &[u8]) -> TextEncoding {
+    match head {
+        [0xEF, 0xBB, 0xBF, ..] => return TextEncoding::Utf8Bom,
+        [0xFF, 0xFE, ..] => return TextEncoding::Utf16Le,
+        [0xFE, 0xFF, ..] => return TextEncoding::Utf16Be,
+        _ => {}
+    }
+
+    // 无 BOM 的 UTF-16：ASCII 部分（时间、路径）会在固定奇偶位置产生大量 0 字节
+    let pairs = head.len() / 2;
+    if pairs > 0 {
+        let even_zeros = head.iter().step_by(2).filter(|&&b| b == 0).count();
+        let odd_zeros = head.iter().skip(1).step_by(2).filter(|&&b| b == 0).count();
+        if odd_zeros * 5 > pairs && even_zeros * 20 < odd_zeros {
+            return TextEncoding::Utf16Le;
+        }
+        if even_zeros * 5 > pairs && odd_zeros * 20 < even_zeros {
+            return TextEncoding::Utf16Be;
+        }
+    }
+
+    match std::str::from_utf8(head) {
+        Ok(_) => TextEncoding::Utf8,
+        // 头部恰好截断在多字节字符中间，仍视为 UTF-8
+        Err(e) if e.error_len().is_none() => TextEncoding::Utf8,
+        Err(_) => TextEncoding::Gb18030,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const SAMPLE: &str = "2024-05-01 12:00:00\n操作进程: C:\\Program Files\\ACE\\SGuard64.exe\n操作结果: 已阻止\n";
+
+    fn utf16(s: &str, big_endian: bool) -> Vec<u8> {
+        s.encode_utf16()
+            .flat_map(|u| if big_endian { u.to_be_bytes() } else { u.to_le_bytes() })
+            .collect()
+    }
+
+    #[test]
+    fn detects_boms() {
+        let mut utf8 = vec![0xEF, 0xBB, 0xBF];
+        utf8.extend_from_slice(SAMPLE.as_bytes());
+        assert_eq!(detect_encoding(&utf8), TextEncoding::Utf8Bom);
+
+        let mut le = vec![0xFF, 0xFE];
+        le.extend(utf16(SAMPLE, false));
+        assert_eq!(detect_encoding(&le), TextEncoding::Utf16Le);
+
+        let mut be = vec![0xFE, 0xFF];
+        be.extend(utf16(SAMPLE, true));
+        assert_eq!(detect_encoding(&be), TextEncoding::Utf16Be);
+    }
+
+    #[test]
+    fn detects_utf16_without_bom() {
+        assert_eq!(detect_encoding(&utf16(SAMPLE, false)), TextEncoding::Utf16Le);
+        assert_eq!(detect_encoding(&utf16(SAMPLE, true)), TextEncoding::Utf16Be);
+    }
+
+    #[test]
+    fn detects_utf8_and_gbk() {
+        assert_eq!(detect_encoding(SAMPLE.as_bytes()), TextEncoding::Utf8);
+        let (gbk, _, _) = GB18030.encode(SAMPLE);
+        assert_eq!(detect_encoding(&gbk), TextEncoding::Gb18030);
+    }
+
+    #[test]
+    fn truncated_utf8_head_is_still_utf8() {
+        let bytes = SAMPLE.as_bytes();
+        let cut = SAMPLE.find("进程").unwrap() + 1;
+        assert_eq!(detect_encoding(&bytes[..cut]), TextEncoding::Utf8);
+    }
+}
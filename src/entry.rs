@@ -0,0 +1,177 @@
+use chrono::NaiveDateTime;
+use serde::{Deserialize, Serialize};
+
+use crate::operation::OperationKind;
+use crate::outcome::OperationOutcome;
+
+/// 火绒日志条目首行可能出现的时间格式
+const TIMESTAMP_FORMATS: &[&str] = &["%Y-%m-%d %H:%M:%S%.f", "%Y/%m/%d %H:%M:%S%.f"];
+
+/// 条目内所有字段标签，提取某字段时以其余标签作为终止符
+const FIELD_LABELS: &[&str] = &[
+    "操作进程：",
+    "操作进程命令行：",
+    "触犯规则：",
+    "操作类型：",
+    "操作文件：",
+    "操作结果：",
+    "\r\n",
+    "\n",
+];
+
+/// 单条 ACE 扫盘日志记录（对应火绒日志中的一个条目）
+///
+/// 序列化时省略缺失的字段和规范化前的原文，历史库中只保存规范化后的路径。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AceLogEntry {
+    /// 条目首行的时间戳，如 `2024-05-01 12:34:56`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub timestamp: Option<NaiveDateTime>,
+    /// 操作进程完整路径
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub process_path: Option<String>,
+    /// 操作进程命令行
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub command_line: Option<String>,
+    /// 操作类型
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub operation_type: Option<String>,
+    /// 操作文件（扫描目标）
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub target_file: Option<String>,
+    /// 触犯的火绒规则名
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub rule_name: Option<String>,
+    /// 操作结果，如 `已阻止`
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub result: Option<String>,
+    /// 规范化前的操作文件原文，见 [`PathNormalizer`](crate::PathNormalizer)
+    #[serde(skip)]
+    pub raw_target_file: Option<String>,
+    /// 规范化前的进程路径原文
+    #[serde(skip)]
+    pub raw_process_path: Option<String>,
+}
+
+impl AceLogEntry {
+    /// 从单个日志条目文本中解析各字段
+    pub fn parse(entry: &str) -> Self {
+        let field = |label: &str| {
+            extract_field(entry, label, FIELD_LABELS)
+                .map(str::trim)
+                .filter(|v| !v.is_empty())
+                .map(str::to_string)
+        };
+
+        AceLogEntry {
+            timestamp: extract_timestamp(entry),
+            process_path: field("操作进程："),
+            command_line: field("操作进程命令行："),
+            operation_type: field("操作类型："),
+            target_file: field("操作文件："),
+            rule_name: field("触犯规则："),
+            result: field("操作结果："),
+            raw_target_file: None,
+            raw_process_path: None,
+        }
+    }
+
+    /// 条目身份：时间戳、进程、扫描目标、操作类型与规则都相同即视为同一事件
+    pub fn identity(&self) -> EntryKey {
+        EntryKey {
+            timestamp: self.timestamp,
+            process_path: self.process_path.clone(),
+            target_file: self.target_file.clone(),
+            operation_type: self.operation_type.clone(),
+            rule_name: self.rule_name.clone(),
+        }
+    }
+
+    /// 命令行中可执行文件之后的启动参数，没有参数时为空字符串；日志中没有命令行时为 `None`
+    pub fn arguments(&self) -> Option<&str> {
+        self.command_line.as_deref().map(command_arguments)
+    }
+
+    /// 操作类型的归类，日志中没有操作类型时为 `None`
+    pub fn operation(&self) -> Option<OperationKind> {
+        self.operation_type.as_deref().map(OperationKind::classify)
+    }
+
+    /// 操作结果的归类，日志中没有操作结果时为 [`OperationOutcome::Unknown`]
+    pub fn outcome(&self) -> OperationOutcome {
+        self.result
+            .as_deref()
+            .map_or(OperationOutcome::Unknown, OperationOutcome::classify)
+    }
+
+    /// 是否被火绒成功阻止（含询问后阻止）
+    pub fn is_blocked(&self) -> bool {
+        self.outcome().is_blocked()
+    }
+
+    /// 进程名（完整路径的最后一段）
+    pub fn process_name(&self) -> Option<&str> {
+        self.process_path
+            .as_deref()
+            .and_then(|p| p.split('\\').next_back())
+            .map(str::trim)
+    }
+}
+
+/// 去掉命令行开头的可执行文件：带引号时到右引号为止，否则到 `.exe` 或第一个空白为止
+fn command_arguments(command_line: &str) -> &str {
+    let command_line = command_line.trim();
+    let rest = if let Some(quoted) = command_line.strip_prefix('"') {
+        quoted.find('"').map_or("", |end| &quoted[end + 1..])
+    } else if let Some(pos) = command_line.to_ascii_lowercase().find(".exe") {
+        &command_line[pos + ".exe".len()..]
+    } else {
+        command_line
+            .find(char::is_whitespace)
+            .map_or("", |pos| &command_line[pos..])
+    };
+    rest.trim()
+}
+
+/// 日志条目的身份，见 [`AceLogEntry::identity`]
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct EntryKey {
+    pub timestamp: Option<NaiveDateTime>,
+    pub process_path: Option<String>,
+    pub target_file: Option<String>,
+    pub operation_type: Option<String>,
+    pub rule_name: Option<String>,
+}
+
+fn extract_field<'a>(text: &'a str, prefix: &str, terminators: &[&str]) -> Option<&'a str> {
+    text.find(prefix).and_then(|start| {
+        let value_start = start + prefix.len();
+        if value_start >= text.len() {
+            return None;
+        }
+        
+        let value_end = terminators
+            .iter()
+            .filter_map(|term| text[value_start..].find(term))
+            .min()
+            .map(|pos| value_start + pos)
+            .unwrap_or(text.len());
+        
+        if value_start >= value_end {
+            None
+        } else {
+            Some(&text[value_start..value_end])
+        }
+    })
+}
+
+/// 解析条目首个非空行的「日期 时间」部分
+fn extract_timestamp(entry: &str) -> Option<NaiveDateTime> {
+    let first_line = entry.lines().map(str::trim).find(|l| !l.is_empty())?;
+    let mut parts = first_line.split_whitespace();
+    let text = format!("{} {}", parts.next()?, parts.next()?);
+    TIMESTAMP_FORMATS
+        .iter()
+        .find_map(|fmt| NaiveDateTime::parse_from_str(&text, fmt).ok())
+}
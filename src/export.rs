@@ -0,0 +1,97 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use crate::matrix::CountMatrix;
+use crate::model::extension_label;
+use crate::options::ReportOptions;
+use crate::stats::AceScanStats;
+use crate::winpath::WinPath;
+
+/// 默认导出的高频扫描目标清单文件名
+pub const HIGH_RISK_CSV: &str = "high_risk_targets.csv";
+/// 进程 × 分类交叉表文件名
+pub const PROCESS_CATEGORY_CSV: &str = "process_categories.csv";
+/// 进程 × 扩展名交叉表文件名
+pub const PROCESS_EXTENSION_CSV: &str = "process_extensions.csv";
+
+/// 生成高频扫描目标清单 CSV（带 UTF-8 BOM，Excel/WPS 可直接打开）
+pub fn high_risk_targets_csv(stats: &AceScanStats, options: &ReportOptions) -> Vec<u8> {
+    let mut files: Vec<_> = stats.unique_files.iter().collect();
+    files.sort_by(|a, b| b.1.cmp(a.1));
+
+    let m = options.locale.messages();
+    let mut csv = format!("{}\n", m.csv_header);
+
+    for (i, (file, count)) in files.iter().enumerate().take(options.export_limit) {
+        let count_val = **count;
+        let risk = options.file_risk.level(count_val).label(options.locale);
+        let ext = WinPath::parse(file)
+            .extension()
+            .unwrap_or_else(|| m.no_extension.to_string());
+
+        let safe_file = if file.contains(',') || file.contains('\n') || file.contains('\"') {
+            format!("\"{}\"", file.replace('\"', "\"\""))
+        } else {
+            file.to_string()
+        };
+        
+        // 添加完整路径列（方便直接复制到火绒规则）
+        csv.push_str(&format!("{},{},{},{},{},\"{}\"\n", i + 1, count_val, safe_file, risk, ext, file));
+    }
+
+    with_bom(&csv)
+}
+
+/// 生成进程 × 分类交叉表 CSV（含行列合计）
+pub fn process_category_csv(stats: &AceScanStats, options: &ReportOptions) -> Vec<u8> {
+    let m = options.locale.messages();
+    let csv = CountMatrix::from_nested(&stats.process_categories).to_csv(
+        m.col_process,
+        m.total,
+        str::to_string,
+        |id| options.category_label(id).to_string(),
+    );
+    with_bom(&csv)
+}
+
+/// 生成进程 × 扩展名交叉表 CSV（含行列合计）
+pub fn process_extension_csv(stats: &AceScanStats, options: &ReportOptions) -> Vec<u8> {
+    let m = options.locale.messages();
+    let csv = CountMatrix::from_nested(&stats.process_extensions).to_csv(
+        m.col_process,
+        m.total,
+        str::to_string,
+        |ext| extension_label(ext, m),
+    );
+    with_bom(&csv)
+}
+
+/// 添加UTF-8 BOM解决Excel乱码
+fn with_bom(csv: &str) -> Vec<u8> {
+    let mut bom_csv = Vec::from(&[0xEFu8, 0xBB, 0xBF][..]);
+    bom_csv.extend_from_slice(csv.as_bytes());
+    bom_csv
+}
+
+/// 将高频扫描目标清单写入 `path`
+pub fn export_high_risk_targets(
+    stats: &AceScanStats,
+    options: &ReportOptions,
+    path: &Path,
+) -> io::Result<()> {
+    fs::write(path, high_risk_targets_csv(stats, options))
+}
+
+/// 将进程 × 分类、进程 × 扩展名两张交叉表写入 `dir`，返回写入的文件路径
+pub fn export_process_matrices(
+    stats: &AceScanStats,
+    options: &ReportOptions,
+    dir: &Path,
+) -> io::Result<[PathBuf; 2]> {
+    let category_path = dir.join(PROCESS_CATEGORY_CSV);
+    let extension_path = dir.join(PROCESS_EXTENSION_CSV);
+    fs::write(&category_path, process_category_csv(stats, options))?;
+    fs::write(&extension_path, process_extension_csv(stats, options))?;
+    Ok([category_path, extension_path])
+}
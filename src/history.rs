@@ -0,0 +1,430 @@
+use std::collections::{BTreeMap, BTreeSet, HashMap};
+use std::fs::{self, File, OpenOptions};
+use std::io::{self, BufRead, BufReader, BufWriter, Write};
+use std::path::{Path, PathBuf};
+
+use chrono::{Datelike, NaiveDate};
+use serde::{Deserialize, Serialize};
+
+use crate::dedup::EntryDeduplicator;
+use crate::entry::AceLogEntry;
+
+/// 历史库默认目录名（位于输出目录下）
+pub const HISTORY_DIR: &str = "ace_history";
+/// 历史库中保存条目的文件，每行一条 JSON 记录，只追加不改写
+pub const HISTORY_ENTRIES_FILE: &str = "entries.jsonl";
+
+/// 历史库中的一条记录：来源机器、来源日志与规范化后的日志条目
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryRecord {
+    /// 日志所在机器的名称（导入时统一为大写，见 [`machine_key`]），用于区分多台电脑导出的日志
+    pub machine: String,
+    /// 首次导入该条目的日志文件
+    pub source: String,
+    #[serde(flatten)]
+    pub entry: AceLogEntry,
+}
+
+/// 历史查询条件：按日期范围（含两端）和机器筛选
+///
+/// 指定了日期范围时，没有时间戳的条目不会被选中。
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct HistoryFilter {
+    pub since: Option<NaiveDate>,
+    pub until: Option<NaiveDate>,
+    pub machine: Option<String>,
+}
+
+impl HistoryFilter {
+    /// 记录是否满足查询条件
+    pub fn matches(&self, record: &HistoryRecord) -> bool {
+        if self.machine.as_ref().is_some_and(|m| !m.eq_ignore_ascii_case(&record.machine)) {
+            return false;
+        }
+        if self.since.is_none() && self.until.is_none() {
+            return true;
+        }
+        record.entry.timestamp.is_some_and(|ts| {
+            let day = ts.date();
+            self.since.is_none_or(|since| day >= since) && self.until.is_none_or(|until| day <= until)
+        })
+    }
+}
+
+/// 一次导入的结果
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct IngestSummary {
+    /// 新写入历史库的条目数
+    pub added: usize,
+    /// 历史库中已有而跳过的条目数
+    pub duplicates: usize,
+    /// 导入后历史库中的条目总数
+    pub total: usize,
+}
+
+/// 本地扫盘历史库：累积多次导入的日志条目，用于跨周、跨机器查看趋势
+///
+/// 条目以 JSON Lines 追加写入 [`HISTORY_ENTRIES_FILE`]，读取时逐行流式解析，不整体载入内存。
+/// 导入时按机器用 [`EntryDeduplicator`] 与已有条目去重，重复导入同一份日志不会放大统计；
+/// 去重索引在首次导入时由已有条目建立，其大小与历史条目数成正比。
+#[derive(Debug)]
+pub struct HistoryStore {
+    dir: PathBuf,
+    /// 每台机器的去重状态，已有条目视为之前的输入；首次导入时才建立
+    index: Option<HashMap<String, EntryDeduplicator>>,
+    /// 建立去重索引后的条目总数
+    len: usize,
+}
+
+impl HistoryStore {
+    /// 打开 `dir` 下的历史库，目录不存在时视为空库（首次导入时创建）
+    pub fn open(dir: &Path) -> io::Result<Self> {
+        Ok(HistoryStore {
+            dir: dir.to_path_buf(),
+            index: None,
+            len: 0,
+        })
+    }
+
+    /// 历史库目录
+    pub fn dir(&self) -> &Path {
+        &self.dir
+    }
+
+    /// 条目文件路径
+    pub fn entries_path(&self) -> PathBuf {
+        self.dir.join(HISTORY_ENTRIES_FILE)
+    }
+
+    /// 将 `machine` 上来自 `source` 的日志条目逐条导入历史库，已存在的条目跳过
+    ///
+    /// `entries` 可直接传入 [`LogFile::entries`](crate::LogFile::entries) 这样的流式读取结果，
+    /// 读取出错时停止导入并返回该错误，之前已写入的条目保留。
+    pub fn ingest<I>(&mut self, machine: &str, source: &str, entries: I) -> io::Result<IngestSummary>
+    where
+        I: IntoIterator<Item = io::Result<AceLogEntry>>,
+    {
+        self.load_index()?;
+        let machine = machine_key(machine);
+        let mut summary = IngestSummary::default();
+        let mut writer = None;
+        let dedup = self
+            .index
+            .get_or_insert_default()
+            .entry(machine.clone())
+            .or_default();
+        for entry in entries {
+            let mut entry = entry?;
+            if !dedup.insert(&entry) {
+                summary.duplicates += 1;
+                continue;
+            }
+            // 规范化前的原文不落盘
+            entry.raw_target_file = None;
+            entry.raw_process_path = None;
+            let record = HistoryRecord {
+                machine: machine.clone(),
+                source: source.to_string(),
+                entry,
+            };
+
+            let writer = match &mut writer {
+                Some(writer) => writer,
+                None => {
+                    fs::create_dir_all(&self.dir)?;
+                    let file = OpenOptions::new()
+                        .create(true)
+                        .append(true)
+                        .open(self.dir.join(HISTORY_ENTRIES_FILE))?;
+                    writer.insert(BufWriter::new(file))
+                }
+            };
+            serde_json::to_writer(&mut *writer, &record)?;
+            writeln!(writer)?;
+            summary.added += 1;
+        }
+        dedup.next_input();
+        if let Some(mut writer) = writer {
+            writer.flush()?;
+        }
+
+        self.len += summary.added;
+        summary.total = self.len;
+        Ok(summary)
+    }
+
+    /// 由已有条目建立去重索引（只在首次导入时进行）
+    fn load_index(&mut self) -> io::Result<()> {
+        if self.index.is_some() {
+            return Ok(());
+        }
+        let mut index: HashMap<String, EntryDeduplicator> = HashMap::new();
+        let mut len = 0;
+        for record in self.records()? {
+            let record = record?;
+            // 早期版本按原样保存机器名，建索引时同样统一大小写
+            index
+                .entry(machine_key(&record.machine))
+                .or_default()
+                .insert(&record.entry);
+            len += 1;
+        }
+        index.values_mut().for_each(EntryDeduplicator::next_input);
+        self.index = Some(index);
+        self.len = len;
+        Ok(())
+    }
+
+    /// 逐条读取历史库中的全部记录（按导入顺序）
+    pub fn records(&self) -> io::Result<impl Iterator<Item = io::Result<HistoryRecord>> + use<>> {
+        let path = self.entries_path();
+        let file = match File::open(&path) {
+            Ok(file) => Some(file),
+            Err(e) if e.kind() == io::ErrorKind::NotFound => None,
+            Err(e) => return Err(e),
+        };
+        let lines = file.into_iter().flat_map(|f| BufReader::new(f).lines());
+        Ok(lines
+            .enumerate()
+            .filter_map(move |(index, line)| parse_record(&path, index, line)))
+    }
+
+    /// 逐条读取满足 `filter` 的记录
+    pub fn query(
+        &self,
+        filter: &HistoryFilter,
+    ) -> io::Result<impl Iterator<Item = io::Result<HistoryRecord>> + use<>> {
+        let filter = filter.clone();
+        Ok(self
+            .records()?
+            .filter(move |r| r.as_ref().map_or(true, |r| filter.matches(r))))
+    }
+}
+
+/// 解析条目文件中的一行，空行跳过
+fn parse_record(
+    path: &Path,
+    index: usize,
+    line: io::Result<String>,
+) -> Option<io::Result<HistoryRecord>> {
+    let line = match line {
+        Ok(line) => line,
+        Err(e) => return Some(Err(e)),
+    };
+    if line.trim().is_empty() {
+        return None;
+    }
+    Some(serde_json::from_str(&line).map_err(|e| {
+        io::Error::new(
+            io::ErrorKind::InvalidData,
+            format!("{}:{}: {}", path.display(), index + 1, e),
+        )
+    }))
+}
+
+/// 某一周、某台机器的扫描汇总
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct TrendCounts {
+    pub scans: usize,
+    pub blocked: usize,
+    /// 被放行（含询问后允许）的次数
+    pub allowed: usize,
+}
+
+/// 历史趋势：按 ISO 周（如 `2024-W18`）与机器汇总扫描、拦截与放行次数
+///
+/// 没有时间戳的条目计入空字符串表示的周。
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct HistoryTrend {
+    /// 周 → 机器 → 汇总
+    pub weeks: BTreeMap<String, BTreeMap<String, TrendCounts>>,
+}
+
+impl HistoryTrend {
+    /// 计入一条历史记录
+    pub fn record(&mut self, record: &HistoryRecord) {
+        let week = record.entry.timestamp.map_or_else(String::new, |ts| {
+            let week = ts.iso_week();
+            format!("{}-W{:02}", week.year(), week.week())
+        });
+        let outcome = record.entry.outcome();
+        let counts = self
+            .weeks
+            .entry(week)
+            .or_default()
+            .entry(machine_key(&record.machine))
+            .or_default();
+        counts.scans += 1;
+        counts.blocked += usize::from(outcome.is_blocked());
+        counts.allowed += usize::from(outcome.is_allowed());
+    }
+
+    /// 涉及的不同机器数
+    pub fn machines(&self) -> usize {
+        self.weeks
+            .values()
+            .flat_map(|machines| machines.keys())
+            .collect::<BTreeSet<_>>()
+            .len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.weeks.is_empty()
+    }
+}
+
+/// 历史库中保存的机器名：去掉首尾空白并转为大写，与 Windows 计算机名的写法一致，
+/// 使 `PC1` 与 `pc1` 视为同一台机器
+pub fn machine_key(name: &str) -> String {
+    name.trim().to_uppercase()
+}
+
+/// 本机名称：Windows 取 `COMPUTERNAME`，其他系统取 `HOSTNAME`，都没有时为 `local`
+pub fn local_machine_name() -> String {
+    ["COMPUTERNAME", "HOSTNAME"]
+        .iter()
+        .filter_map(|var| std::env::var(var).ok())
+        .map(|name| name.trim().to_string())
+        .find(|name| !name.is_empty())
+        .unwrap_or_else(|| "local".to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    use chrono::NaiveDateTime;
+
+    /// 测试用的临时历史库目录，离开作用域时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let name = format!("fk-deltaforce-{}-{}", name, std::process::id());
+            let dir = std::env::temp_dir().join(name);
+            let _ = fs::remove_dir_all(&dir);
+            TempDir(dir)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    fn entry(timestamp: &str, target: &str, result: &str) -> AceLogEntry {
+        AceLogEntry {
+            timestamp: NaiveDateTime::parse_from_str(timestamp, "%Y-%m-%d %H:%M:%S").ok(),
+            process_path: Some(r"C:\Program Files\AntiCheatExpert\SGuard64.exe".to_string()),
+            target_file: Some(target.to_string()),
+            result: Some(result.to_string()),
+            ..AceLogEntry::default()
+        }
+    }
+
+    fn log() -> Vec<AceLogEntry> {
+        vec![
+            entry("2024-04-28 23:59:59", r"C:\Windows\a.dll", "已阻止"),
+            entry("2024-04-29 00:00:00", r"C:\Windows\b.dll", "已放行"),
+            entry("2024-05-01 12:00:00", r"C:\Windows\a.dll", "已阻止"),
+        ]
+    }
+
+    fn ingest(store: &mut HistoryStore, machine: &str, entries: Vec<AceLogEntry>) -> IngestSummary {
+        store.ingest(machine, "ace.log", entries.into_iter().map(Ok)).unwrap()
+    }
+
+    fn all(store: &HistoryStore, filter: &HistoryFilter) -> Vec<HistoryRecord> {
+        store.query(filter).unwrap().collect::<io::Result<_>>().unwrap()
+    }
+
+    fn day(text: &str) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(text, "%Y-%m-%d").ok()
+    }
+
+    #[test]
+    fn reingesting_the_same_log_adds_nothing() {
+        let dir = TempDir::new("reingest");
+        let mut store = HistoryStore::open(&dir.0).unwrap();
+        let first = ingest(&mut store, "PC1", log());
+        assert_eq!((first.added, first.duplicates, first.total), (3, 0, 3));
+        let again = ingest(&mut store, "PC1", log());
+        assert_eq!((again.added, again.duplicates, again.total), (0, 3, 3));
+
+        // 重新打开时由文件重建去重索引，机器名大小写不同也视为同一台
+        let mut reopened = HistoryStore::open(&dir.0).unwrap();
+        let third = ingest(&mut reopened, " pc1 ", log());
+        assert_eq!((third.added, third.duplicates, third.total), (0, 3, 3));
+
+        // 其他机器上的同样日志照常导入
+        let other = ingest(&mut reopened, "PC2", log());
+        assert_eq!((other.added, other.total), (3, 6));
+
+        let records = all(&reopened, &HistoryFilter::default());
+        assert_eq!(records.len(), 6);
+        assert!(records.iter().all(|r| r.machine == "PC1" || r.machine == "PC2"));
+        assert!(records.iter().all(|r| r.entry.raw_target_file.is_none()));
+    }
+
+    #[test]
+    fn filters_by_date_range_and_machine() {
+        let dir = TempDir::new("filter");
+        let mut store = HistoryStore::open(&dir.0).unwrap();
+        ingest(&mut store, "pc1", log());
+        ingest(&mut store, "pc2", vec![entry("2024-04-29 08:00:00", r"D:\x.pak", "已阻止")]);
+        let mut undated = entry("2024-04-29 08:00:00", r"D:\y.pak", "已阻止");
+        undated.timestamp = None;
+        ingest(&mut store, "pc2", vec![undated]);
+
+        let filter = HistoryFilter {
+            since: day("2024-04-29"),
+            until: day("2024-04-30"),
+            machine: None,
+        };
+        let targets: Vec<_> = all(&store, &filter)
+            .into_iter()
+            .filter_map(|r| r.entry.target_file)
+            .collect();
+        assert_eq!(targets, [r"C:\Windows\b.dll", r"D:\x.pak"]);
+
+        let filter = HistoryFilter {
+            machine: Some("Pc2".to_string()),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(all(&store, &filter).len(), 2);
+        let filter = HistoryFilter {
+            until: day("2024-04-28"),
+            ..HistoryFilter::default()
+        };
+        assert_eq!(all(&store, &filter).len(), 1);
+    }
+
+    #[test]
+    fn trend_buckets_by_iso_week_and_machine() {
+        let mut trend = HistoryTrend::default();
+        let mut records: Vec<_> = log()
+            .into_iter()
+            .map(|entry| HistoryRecord {
+                machine: "PC1".to_string(),
+                source: "ace.log".to_string(),
+                entry,
+            })
+            .collect();
+        records.push(HistoryRecord {
+            machine: "pc1".to_string(),
+            source: "old.log".to_string(),
+            entry: AceLogEntry::default(),
+        });
+        records.iter().for_each(|r| trend.record(r));
+
+        // 2024-04-28 是周日，属于第 17 周；2024-04-29 周一开始第 18 周
+        let weeks: Vec<_> = trend.weeks.keys().map(String::as_str).collect();
+        assert_eq!(weeks, ["", "2024-W17", "2024-W18"]);
+        let counts = |week: &str| trend.weeks[week]["PC1"];
+        assert_eq!(counts("2024-W17"), TrendCounts { scans: 1, blocked: 1, allowed: 0 });
+        assert_eq!(counts("2024-W18"), TrendCounts { scans: 2, blocked: 1, allowed: 1 });
+        assert_eq!(counts("").scans, 1);
+        assert_eq!(trend.machines(), 1);
+    }
+}
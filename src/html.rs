@@ -0,0 +1,372 @@
+use std::fmt::{self, Write};
+
+use crate::i18n::{fill, Locale, Messages};
+use crate::model::{Block, ColumnKind, Metric, ReportModel, Table};
+use crate::options::ReportOptions;
+use crate::session::ScanSession;
+use crate::stats::AceScanStats;
+use crate::tree::{DirNode, DirTree};
+
+/// 行数超过该值的表格放入可滚动区域
+const SCROLL_ROWS: usize = 20;
+
+const STYLE: &str = r#"
+body { font-family: "Segoe UI", "Microsoft YaHei", sans-serif; margin: 0; background: #f4f5f7; color: #222; }
+header { background: #1f2937; color: #fff; padding: 24px 32px; }
+header h1 { margin: 0 0 8px; font-size: 24px; }
+header p { margin: 0; color: #cbd5e1; }
+main { max-width: 1200px; margin: 0 auto; padding: 16px 32px 48px; }
+section { background: #fff; border-radius: 8px; padding: 16px 20px; margin-top: 16px; box-shadow: 0 1px 3px rgba(0,0,0,.08); }
+h2 { font-size: 18px; margin: 0 0 12px; }
+h3 { font-size: 15px; margin: 16px 0 8px; }
+.cards { display: grid; grid-template-columns: repeat(auto-fit, minmax(180px, 1fr)); gap: 12px; margin-bottom: 12px; }
+.card { background: #f9fafb; border-radius: 8px; padding: 16px; }
+.card .value { font-size: 26px; font-weight: 600; }
+.card .label, .card .note { color: #6b7280; font-size: 13px; }
+table { margin-bottom: 12px; border-collapse: collapse; width: 100%; font-size: 13px; }
+th, td { padding: 6px 8px; border-bottom: 1px solid #e5e7eb; text-align: left; }
+th { background: #f9fafb; cursor: pointer; user-select: none; white-space: nowrap; }
+th.sorted-asc::after { content: " ▲"; }
+th.sorted-desc::after { content: " ▼"; }
+td.num { text-align: right; font-variant-numeric: tabular-nums; }
+td.path { font-family: Consolas, monospace; word-break: break-all; }
+tfoot td { font-weight: 600; background: #f9fafb; }
+.scroll { max-height: 480px; overflow: auto; }
+.chart rect { fill: #3b82f6; }
+.chart text { font-size: 10px; fill: #6b7280; }
+.heat td { text-align: center; font-size: 11px; padding: 4px 2px; }
+.tree ul { list-style: none; padding-left: 18px; margin: 0; }
+.tree summary, .tree li.file { font-family: Consolas, monospace; font-size: 13px; padding: 2px 0; }
+.tree .count { color: #2563eb; margin-left: 8px; }
+.tree .self { color: #9ca3af; margin-left: 4px; }
+"#;
+
+const SORT_SCRIPT: &str = r#"
+document.querySelectorAll("table.sortable").forEach(function (table) {
+  table.querySelectorAll("th").forEach(function (th, col) {
+    th.addEventListener("click", function () {
+      var asc = !th.classList.contains("sorted-asc");
+      table.querySelectorAll("th").forEach(function (h) { h.classList.remove("sorted-asc", "sorted-desc"); });
+      th.classList.add(asc ? "sorted-asc" : "sorted-desc");
+      var body = table.tBodies[0];
+      var key = function (row) {
+        var raw = row.cells[col].textContent;
+        var num = parseFloat(raw);
+        return isNaN(num) ? raw.toLowerCase() : num;
+      };
+      Array.from(body.rows)
+        .sort(function (a, b) { var x = key(a), y = key(b); return (x < y ? -1 : x > y ? 1 : 0) * (asc ? 1 : -1); })
+        .forEach(function (row) { body.appendChild(row); });
+    });
+  });
+});
+"#;
+
+/// 生成离线单文件 HTML 版详细分析报告（与终端、Markdown 报告同一数据模型）
+pub fn render_html_report(
+    stats: &AceScanStats,
+    sessions: &[ScanSession],
+    options: &ReportOptions,
+) -> String {
+    render_html(&ReportModel::detailed(stats, sessions, options))
+}
+
+/// 将任意报告模型渲染为离线单文件 HTML（内联 CSS/SVG/脚本，不依赖任何外部资源）
+pub fn render_html(model: &ReportModel) -> String {
+    let mut out = String::new();
+    write_html(&mut out, model).expect("写入 String 不会失败");
+    out
+}
+
+/// 以 HTML 排版输出报告模型：柱状图绘制为 SVG，目录树可折叠，表格可点击表头排序
+pub fn write_html<W: Write>(out: &mut W, model: &ReportModel) -> fmt::Result {
+    let m = model.locale.messages();
+    // 标题去掉图标后用作页面标题；没有报告标题时取第一节标题
+    let page_title = model
+        .title
+        .as_deref()
+        .or_else(|| model.sections.first().map(|s| s.title.as_str()))
+        .unwrap_or_default()
+        .trim_start_matches(|c: char| !c.is_alphanumeric())
+        .trim();
+
+    writeln!(
+        out,
+        "<!DOCTYPE html>\n<html lang=\"{}\">\n<head>\n<meta charset=\"utf-8\">",
+        model.locale.code()
+    )?;
+    writeln!(out, "<title>{}</title>", escape(page_title))?;
+    writeln!(out, "<style>{}</style>\n</head>\n<body>", STYLE)?;
+
+    if let Some(title) = &model.title {
+        writeln!(out, "<header><h1>{}</h1>", escape(title))?;
+        if let Some(subtitle) = &model.subtitle {
+            let subtitle = subtitle.trim_start_matches('(').trim_end_matches(')');
+            writeln!(out, "<p>{}</p>", escape(subtitle))?;
+        }
+        writeln!(out, "</header>")?;
+    }
+    writeln!(out, "<main>")?;
+
+    for section in &model.sections {
+        writeln!(out, "<section><h2>{}</h2>", escape(&section.title))?;
+        for block in &section.blocks {
+            match block {
+                Block::Metrics(metrics) => write_metrics(out, metrics)?,
+                Block::Table(table) => write_table(out, table)?,
+                Block::Bars { header, bars } => write_bar_chart(out, header, bars)?,
+                Block::Heatmap(rows) => write_heatmap(out, rows, m)?,
+                Block::List(items) => write_list(out, items, model.locale)?,
+                Block::Tree(tree) => write_tree(out, tree, m)?,
+            }
+        }
+        writeln!(out, "</section>")?;
+    }
+
+    writeln!(out, "</main>\n<script>{}</script>\n</body>\n</html>", SORT_SCRIPT)
+}
+
+/// 指标以卡片形式排列
+fn write_metrics<W: Write>(out: &mut W, metrics: &[Metric]) -> fmt::Result {
+    writeln!(out, "<div class=\"cards\">")?;
+    for metric in metrics {
+        write!(
+            out,
+            "<div class=\"card\"><div class=\"value\">{}</div><div class=\"label\">{}</div>",
+            escape(&metric.value),
+            escape(&metric.label)
+        )?;
+        if let Some(note) = &metric.note {
+            write!(out, "<div class=\"note\">{}</div>", escape(note))?;
+        }
+        writeln!(out, "</div>")?;
+    }
+    writeln!(out, "</div>")
+}
+
+/// 可排序表格，数值列右对齐、路径列等宽显示
+fn write_table<W: Write>(out: &mut W, table: &Table) -> fmt::Result {
+    let scroll = table.rows.len() > SCROLL_ROWS;
+    if scroll {
+        write!(out, "<div class=\"scroll\">")?;
+    }
+    let headers: Vec<_> = table.columns.iter().map(|c| c.header.as_str()).collect();
+    write_table_head(out, &headers)?;
+    for row in &table.rows {
+        write_row(out, row, table)?;
+    }
+    write!(out, "</tbody>")?;
+    // 合计行放在 <tfoot> 中，排序时保持在末尾
+    if let Some(footer) = &table.footer {
+        write!(out, "<tfoot>")?;
+        write_row(out, footer, table)?;
+        write!(out, "</tfoot>")?;
+    }
+    write!(out, "</table>")?;
+    if scroll {
+        write!(out, "</div>")?;
+    }
+    writeln!(out)
+}
+
+fn write_row<W: Write>(out: &mut W, row: &[String], table: &Table) -> fmt::Result {
+    write!(out, "<tr>")?;
+    for (cell, column) in row.iter().zip(&table.columns) {
+        let class = match column.kind {
+            ColumnKind::Text => "",
+            ColumnKind::Number => " class=\"num\"",
+            ColumnKind::Path => " class=\"path\"",
+        };
+        write!(out, "<td{}>{}</td>", class, escape(cell))?;
+    }
+    writeln!(out, "</tr>")
+}
+
+fn write_table_head<W: Write>(out: &mut W, headers: &[&str]) -> fmt::Result {
+    write!(out, "<table class=\"sortable\"><thead><tr>")?;
+    for header in headers {
+        write!(out, "<th>{}</th>", escape(header))?;
+    }
+    writeln!(out, "</tr></thead><tbody>")
+}
+
+/// 以内联 SVG 绘制柱状图，标签过长或柱子过密时隔几个标注一次
+fn write_bar_chart<W: Write>(out: &mut W, header: &str, bars: &[(String, usize)]) -> fmt::Result {
+    const WIDTH: f64 = 960.0;
+    const HEIGHT: f64 = 180.0;
+    const LABEL: f64 = 16.0;
+    /// 10px 字号下单个字符的大致宽度
+    const CHAR_WIDTH: f64 = 6.0;
+
+    let peak = bars.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1) as f64;
+    let slot = WIDTH / bars.len().max(1) as f64;
+    let label_chars = bars.iter().map(|(l, _)| l.chars().count()).max().unwrap_or(0);
+    let label_every = ((label_chars as f64 * CHAR_WIDTH + 4.0) / slot).ceil().max(1.0) as usize;
+
+    writeln!(
+        out,
+        "<svg class=\"chart\" viewBox=\"0 0 {} {}\" width=\"100%\" role=\"img\" aria-label=\"{}\">",
+        WIDTH,
+        HEIGHT + LABEL,
+        escape(header)
+    )?;
+    for (i, (label, count)) in bars.iter().enumerate() {
+        let h = *count as f64 / peak * (HEIGHT - 4.0);
+        let x = i as f64 * slot;
+        writeln!(
+            out,
+            "<rect x=\"{:.1}\" y=\"{:.1}\" width=\"{:.1}\" height=\"{:.1}\"><title>{}: {}</title></rect>",
+            x + slot * 0.1,
+            HEIGHT - h,
+            slot * 0.8,
+            h,
+            escape(label),
+            count
+        )?;
+        if i % label_every == 0 {
+            writeln!(
+                out,
+                "<text x=\"{:.1}\" y=\"{}\" text-anchor=\"middle\">{}</text>",
+                x + slot / 2.0,
+                HEIGHT + LABEL - 4.0,
+                escape(label)
+            )?;
+        }
+    }
+    writeln!(out, "</svg>")
+}
+
+/// 日期 × 小时热力表，颜色深浅按峰值比例
+fn write_heatmap<W: Write>(
+    out: &mut W,
+    rows: &[(String, [usize; 24])],
+    m: &Messages,
+) -> fmt::Result {
+    writeln!(
+        out,
+        "<div class=\"scroll\"><table class=\"heat\"><tr><th>{}</th>",
+        escape(m.col_date)
+    )?;
+    for h in 0..24 {
+        write!(out, "<th>{}</th>", h)?;
+    }
+    writeln!(out, "<th>{}</th></tr>", escape(m.total))?;
+    let peak = rows
+        .iter()
+        .flat_map(|(_, hours)| hours.iter().copied())
+        .max()
+        .unwrap_or(1)
+        .max(1);
+    for (label, hours) in rows {
+        write!(out, "<tr><td>{}</td>", escape(label))?;
+        for c in hours {
+            let alpha = *c as f64 / peak as f64;
+            write!(
+                out,
+                "<td style=\"background: rgba(59,130,246,{:.2})\" title=\"{}\">{}</td>",
+                alpha,
+                c,
+                if *c > 0 { c.to_string() } else { String::new() }
+            )?;
+        }
+        writeln!(out, "<td>{}</td></tr>", hours.iter().sum::<usize>())?;
+    }
+    writeln!(out, "</table></div>")
+}
+
+fn write_list<W: Write>(out: &mut W, items: &[String], locale: Locale) -> fmt::Result {
+    writeln!(out, "<ol>")?;
+    for item in items {
+        // 续行在 HTML 中接在同一段落里，中文无需空格分隔
+        let text: Vec<_> = item.lines().collect();
+        let separator = if locale == Locale::ZhCn { "" } else { " " };
+        writeln!(out, "<li>{}</li>", escape(&text.join(separator)))?;
+    }
+    writeln!(out, "</ol>")
+}
+
+/// 完整的可折叠目录树
+fn write_tree<W: Write>(out: &mut W, tree: &DirTree, m: &Messages) -> fmt::Result {
+    writeln!(out, "<div class=\"tree\"><h3>{}</h3><ul>", escape(m.section_tree))?;
+    for child in tree.root.sorted_children() {
+        write_dir_node(out, child, 0, m.tree_self)?;
+    }
+    writeln!(out, "</ul></div>")
+}
+
+/// 递归输出可折叠的目录节点
+fn write_dir_node<W: Write>(
+    out: &mut W,
+    node: &DirNode,
+    depth: usize,
+    self_label: &str,
+) -> fmt::Result {
+    if node.children.is_empty() {
+        return writeln!(
+            out,
+            "<li class=\"file\">{}<span class=\"count\">{}</span></li>",
+            escape(&node.name),
+            node.subtree_count
+        );
+    }
+
+    write!(
+        out,
+        "<li><details{}><summary>{}<span class=\"count\">{}</span>",
+        if depth < 2 { " open" } else { "" },
+        escape(&node.name),
+        node.subtree_count
+    )?;
+    if node.self_count > 0 {
+        write!(
+            out,
+            "<span class=\"self\">{}</span>",
+            escape(&fill(self_label, &[&node.self_count]))
+        )?;
+    }
+    writeln!(out, "</summary><ul>")?;
+    for child in node.sorted_children() {
+        write_dir_node(out, child, depth + 1, self_label)?;
+    }
+    writeln!(out, "</ul></details></li>")
+}
+
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        match c {
+            '&' => escaped.push_str("&amp;"),
+            '<' => escaped.push_str("&lt;"),
+            '>' => escaped.push_str("&gt;"),
+            '"' => escaped.push_str("&quot;"),
+            '\'' => escaped.push_str("&#39;"),
+            _ => escaped.push(c),
+        }
+    }
+    escaped
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::{parse_ace_logs_precise, ENTRY_SEPARATOR};
+
+    #[test]
+    fn matrix_totals_stay_out_of_the_sortable_body() {
+        let entry = "2024-05-01 12:00:00\n操作进程：C:\\ACE\\SGuard64.exe\n操作类型：读取文件\n\
+                     操作文件：C:\\Windows\\<a>.dll\n操作结果：已阻止\n";
+        let stats = parse_ace_logs_precise(&[entry, entry].join(ENTRY_SEPARATOR));
+        let html = render_html_report(&stats, &[], &ReportOptions::default());
+
+        let footers: Vec<_> = html.match_indices("<tfoot>").collect();
+        assert_eq!(footers.len(), 2);
+        for (start, _) in footers {
+            let table_end = start + html[start..].find("</table>").unwrap();
+            assert!(html[start..table_end].contains("<td>合计</td>"));
+        }
+        // 合计行只出现在 <tfoot> 中
+        assert_eq!(html.matches("<td>合计</td>").count(), 2);
+        assert!(html.contains(r"C:\Windows\&lt;a&gt;.dll"));
+        assert!(!html.contains("<a>"));
+    }
+}
@@ -0,0 +1,576 @@
+use std::fmt::{self, Display};
+use std::str::FromStr;
+
+/// 报告与提示信息使用的语言
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum Locale {
+    /// 简体中文
+    #[default]
+    ZhCn,
+    /// 英文
+    En,
+}
+
+impl Locale {
+    /// 全部支持的语言
+    pub const ALL: [Locale; 2] = [Locale::ZhCn, Locale::En];
+
+    /// 语言代码，如 `zh-CN`
+    pub fn code(self) -> &'static str {
+        match self {
+            Locale::ZhCn => "zh-CN",
+            Locale::En => "en",
+        }
+    }
+
+    /// 按语言代码匹配，接受 `zh`、`zh-CN`、`zh_CN.UTF-8`、`en_US` 等写法
+    pub fn from_code(code: &str) -> Option<Self> {
+        let lang = code
+            .split(['.', '@'])
+            .next()
+            .unwrap_or_default()
+            .split(['-', '_'])
+            .next()
+            .unwrap_or_default();
+        match lang.to_ascii_lowercase().as_str() {
+            "zh" => Some(Locale::ZhCn),
+            "en" => Some(Locale::En),
+            _ => None,
+        }
+    }
+
+    /// 按 `LC_ALL`、`LC_MESSAGES`、`LANG` 环境变量选择语言；均未设置时在 Windows 上取用户的
+    /// 区域设置（`GetUserDefaultLocaleName`，如 `en-US`），仍无法识别时使用简体中文
+    pub fn from_env() -> Self {
+        ["LC_ALL", "LC_MESSAGES", "LANG"]
+            .iter()
+            .filter_map(|name| std::env::var(name).ok())
+            .find(|value| !value.is_empty())
+            .or_else(system_locale_name)
+            .and_then(|value| Self::from_code(&value))
+            .unwrap_or_default()
+    }
+
+    /// 该语言的消息表
+    pub fn messages(self) -> &'static Messages {
+        match self {
+            Locale::ZhCn => &ZH_CN,
+            Locale::En => &EN,
+        }
+    }
+}
+
+impl FromStr for Locale {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        Self::from_code(s).ok_or_else(|| {
+            let codes: Vec<_> = Self::ALL.iter().map(|l| l.code()).collect();
+            format!("不支持的语言 / unsupported language `{}` ({})", s, codes.join(", "))
+        })
+    }
+}
+
+impl Display for Locale {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.code())
+    }
+}
+
+/// Windows 当前用户的区域设置名，如 `zh-CN`
+#[cfg(windows)]
+fn system_locale_name() -> Option<String> {
+    /// `LOCALE_NAME_MAX_LENGTH`，含结尾的 NUL
+    const MAX_LEN: usize = 85;
+
+    #[link(name = "kernel32")]
+    unsafe extern "system" {
+        fn GetUserDefaultLocaleName(locale_name: *mut u16, len: i32) -> i32;
+    }
+
+    let mut buf = [0u16; MAX_LEN];
+    // SAFETY: 缓冲区长度与传入的容量一致，成功时返回写入的字符数（含 NUL）
+    let len = unsafe { GetUserDefaultLocaleName(buf.as_mut_ptr(), MAX_LEN as i32) };
+    let len = usize::try_from(len).ok().filter(|&len| len > 1)?;
+    String::from_utf16(&buf[..len - 1]).ok()
+}
+
+/// 非 Windows 系统只看环境变量
+#[cfg(not(windows))]
+fn system_locale_name() -> Option<String> {
+    None
+}
+
+/// 依次用 `args` 替换模板中的 `{}`
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut args = args.iter();
+    let mut parts = template.split("{}");
+    if let Some(first) = parts.next() {
+        out.push_str(first);
+    }
+    for part in parts {
+        match args.next() {
+            Some(arg) => out.push_str(&arg.to_string()),
+            None => out.push_str("{}"),
+        }
+        out.push_str(part);
+    }
+    out
+}
+
+/// 一种语言下全部面向用户的文本；含 `{}` 的为模板，用 [`fill`] 填充
+#[derive(Debug)]
+pub struct Messages {
+    // 报告标题与各节标题
+    pub report_title: &'static str,
+    pub report_subtitle: &'static str,
+    pub section_core: &'static str,
+    pub section_processes: &'static str,
+    pub section_top_files: &'static str,
+    pub section_categories: &'static str,
+    pub section_extensions: &'static str,
+    pub section_operations: &'static str,
+    pub section_process_matrix: &'static str,
+    pub section_outcomes: &'static str,
+    pub section_allowed_targets: &'static str,
+    pub col_outcome: &'static str,
+    pub col_allowed: &'static str,
+    pub other_columns: &'static str,
+    pub col_operation: &'static str,
+    pub section_time: &'static str,
+    pub section_timeline: &'static str,
+    pub section_heatmap: &'static str,
+    pub section_sessions: &'static str,
+    pub section_rules: &'static str,
+    pub section_tree: &'static str,
+    pub section_hot_dirs: &'static str,
+    pub section_history_trend: &'static str,
+    pub section_recommendations: &'static str,
+
+    // 指标
+    pub metric_total: &'static str,
+    pub metric_blocked: &'static str,
+    pub metric_block_rate: &'static str,
+    pub note_block_rate: &'static str,
+    pub metric_unique_files: &'static str,
+    pub metric_processes: &'static str,
+    pub metric_duplicates: &'static str,
+    pub metric_peak: &'static str,
+    pub peak_value: &'static str,
+    pub metric_first_seen: &'static str,
+    pub metric_last_seen: &'static str,
+    pub metric_span: &'static str,
+    pub metric: &'static str,
+    pub value: &'static str,
+
+    // 表头
+    pub col_rank: &'static str,
+    pub col_process: &'static str,
+    pub col_scans: &'static str,
+    pub col_share: &'static str,
+    pub col_risk: &'static str,
+    pub col_path: &'static str,
+    pub col_hits: &'static str,
+    pub col_category: &'static str,
+    pub col_extension: &'static str,
+    pub col_count: &'static str,
+    pub col_name: &'static str,
+    pub col_rule: &'static str,
+    pub col_start: &'static str,
+    pub col_end: &'static str,
+    pub col_duration: &'static str,
+    pub col_files: &'static str,
+    pub col_process_path: &'static str,
+    pub col_arguments: &'static str,
+    pub col_dir: &'static str,
+    pub col_self: &'static str,
+    pub col_blocked: &'static str,
+    pub col_top_target: &'static str,
+    pub col_hour: &'static str,
+    pub col_week: &'static str,
+    pub col_machine: &'static str,
+    pub col_date: &'static str,
+    pub col_distribution: &'static str,
+    pub total: &'static str,
+    pub no_extension: &'static str,
+    pub no_arguments: &'static str,
+
+    // 图表
+    pub heatmap_legend: &'static str,
+    pub tree_self: &'static str,
+
+    // 风险等级
+    pub risk_high: &'static str,
+    pub risk_medium: &'static str,
+    pub risk_low: &'static str,
+
+    // 操作类型
+    pub op_read: &'static str,
+    pub op_query: &'static str,
+    pub op_enumerate: &'static str,
+    pub op_write: &'static str,
+    pub op_delete: &'static str,
+    pub op_execute: &'static str,
+    pub op_other: &'static str,
+
+    // 操作结果
+    pub outcome_blocked: &'static str,
+    pub outcome_allowed: &'static str,
+    pub outcome_asked_allowed: &'static str,
+    pub outcome_asked_denied: &'static str,
+    pub outcome_unknown: &'static str,
+
+    // 时长
+    pub duration_days: &'static str,
+    pub duration_hours: &'static str,
+    pub duration_minutes: &'static str,
+    pub duration_seconds: &'static str,
+
+    // 对比报告
+    pub diff_title: &'static str,
+    pub section_diff_summary: &'static str,
+    pub section_new_targets: &'static str,
+    pub section_gone_targets: &'static str,
+    pub section_file_changes: &'static str,
+    pub section_category_changes: &'static str,
+    pub section_process_changes: &'static str,
+    pub section_rule_changes: &'static str,
+    pub col_before: &'static str,
+    pub col_after: &'static str,
+    pub col_change: &'static str,
+    pub col_status: &'static str,
+    pub status_added: &'static str,
+    pub status_removed: &'static str,
+    pub no_changes: &'static str,
+    pub percentage_points: &'static str,
+
+    /// 加固建议，条目内的 `\n` 表示续行
+    pub recommendations: [&'static str; 3],
+
+    pub csv_header: &'static str,
+
+    // 命令行提示
+    pub pause_prompt: &'static str,
+    pub status_analyzing: &'static str,
+    pub status_encoding: &'static str,
+    pub status_merged_paths: &'static str,
+    pub status_duplicates: &'static str,
+    pub status_report_written: &'static str,
+    pub status_csv_exported: &'static str,
+    pub status_matrix_exported: &'static str,
+    pub status_ingested: &'static str,
+    pub status_history: &'static str,
+    pub status_history_selected: &'static str,
+
+    // 错误
+    pub error_missing_file: &'static str,
+    pub error_invalid_format: &'static str,
+    pub error_empty_log: &'static str,
+    pub error_invalid_snapshot: &'static str,
+    pub error_snapshot_version: &'static str,
+    pub error_empty_history: &'static str,
+    pub error_categories_io: &'static str,
+    pub error_categories_toml: &'static str,
+    pub error_category_pattern: &'static str,
+    pub error_category_empty: &'static str,
+    pub error_category_folder: &'static str,
+}
+
+/// 简体中文
+pub static ZH_CN: Messages = Messages {
+    report_title: "🛡️ ACE反作弊系统扫盘行为深度分析报告",
+    report_subtitle: "(基于 {} 条有效日志条目)",
+    section_core: "📊 核心指标",
+    section_processes: "🔍 进程行为分析",
+    section_top_files: "⚠️ 高频扫描目标 (Top {})",
+    section_categories: "📁 扫描目标分类统计",
+    section_extensions: "🧩 文件类型分布",
+    section_operations: "🧮 操作类型分析",
+    section_process_matrix: "🔀 进程 × 扫描目标交叉分析",
+    section_outcomes: "🚦 操作结果分析",
+    section_allowed_targets: "🕳️ 未被拦截的扫描目标 (共 {} 个)",
+    col_outcome: "操作结果",
+    col_allowed: "放行次数",
+    other_columns: "其他",
+    col_operation: "操作类型",
+    section_time: "⏰ 扫描行为时间分布",
+    section_timeline: "📅 扫描时间线",
+    section_heatmap: "🗓️ 日期 × 小时分布",
+    section_sessions: "🎮 游戏会话分析 (共 {} 次会话)",
+    section_rules: "📜 触犯规则统计 (共 {} 条规则)",
+    section_tree: "🌲 扫描目标目录树",
+    section_hot_dirs: "🌲 扫描最集中的目录 (展开 {} 层)",
+    section_history_trend: "📈 历史趋势 (按周 × 机器，共 {} 台机器)",
+    section_recommendations: "🛡️ 安全加固建议",
+
+    metric_total: "总扫盘尝试次数",
+    metric_blocked: "成功阻止次数",
+    metric_block_rate: "拦截率",
+    note_block_rate: "拦截率: {}",
+    metric_unique_files: "唯一目标文件数",
+    metric_processes: "活跃进程数",
+    metric_duplicates: "去重丢弃的重叠条目",
+    metric_peak: "扫描高峰",
+    peak_value: "{} (共 {} 次)",
+    metric_first_seen: "首次记录",
+    metric_last_seen: "最后记录",
+    metric_span: "时间跨度",
+    metric: "指标",
+    value: "数值",
+
+    col_rank: "排名",
+    col_process: "进程",
+    col_scans: "扫描次数",
+    col_share: "占比",
+    col_risk: "风险",
+    col_path: "文件路径",
+    col_hits: "频次",
+    col_category: "分类",
+    col_extension: "扩展名",
+    col_count: "次数",
+    col_name: "名称",
+    col_rule: "规则",
+    col_start: "开始",
+    col_end: "结束",
+    col_duration: "时长",
+    col_files: "文件数",
+    col_process_path: "进程路径",
+    col_arguments: "启动参数",
+    col_dir: "目录",
+    col_self: "直接扫描",
+    col_blocked: "已阻止",
+    col_top_target: "主要目标",
+    col_hour: "时段",
+    col_week: "周",
+    col_machine: "机器",
+    col_date: "日期",
+    col_distribution: "分布",
+    total: "合计",
+    no_extension: "无扩展名",
+    no_arguments: "（无参数）",
+
+    heatmap_legend: "图例: · 无  ░ 低  ▒ 中  ▓ 高  █ 峰值",
+    tree_self: "(自身 {})",
+
+    risk_high: "高危",
+    risk_medium: "中危",
+    risk_low: "低危",
+
+    op_read: "读取",
+    op_query: "查询属性",
+    op_enumerate: "枚举目录",
+    op_write: "写入",
+    op_delete: "删除",
+    op_execute: "执行",
+    op_other: "其他",
+
+    outcome_blocked: "已阻止",
+    outcome_allowed: "已放行",
+    outcome_asked_allowed: "询问后允许",
+    outcome_asked_denied: "询问后阻止",
+    outcome_unknown: "未知",
+
+    duration_days: "{} 天 {} 小时 {} 分",
+    duration_hours: "{} 小时 {} 分",
+    duration_minutes: "{} 分 {} 秒",
+    duration_seconds: "{} 秒",
+
+    diff_title: "🔀 ACE扫盘行为对比报告",
+    section_diff_summary: "📊 总体变化",
+    section_new_targets: "🆕 新增扫描目标 (共 {} 个)",
+    section_gone_targets: "🚫 不再扫描的目标 (共 {} 个)",
+    section_file_changes: "📈 扫描频次变化最大的文件 (共 {} 个)",
+    section_category_changes: "📁 分类变化",
+    section_process_changes: "🔍 进程变化",
+    section_rule_changes: "📜 规则变化",
+    col_before: "之前",
+    col_after: "之后",
+    col_change: "变化",
+    col_status: "状态",
+    status_added: "新增",
+    status_removed: "消失",
+    no_changes: "两次统计没有差异",
+    percentage_points: "{} 个百分点",
+
+    recommendations: [
+        "驱动层防护：存储驱动(storqosflt.sys/storvsp.sys)被高频扫描，\n建议对 System32\\drivers 目录设置「仅监控」而非「阻止」",
+        "虚拟化检测：hvhostsvc.dll/vmms.exe 等组件被扫描，\n可能用于检测虚拟机环境，评估是否需放行相关路径",
+        "规则优化：100%拦截率可能导致游戏启动异常，\n建议对反作弊组件自身目录设置「放行」，对驱动目录设置「询问」",
+    ],
+
+    csv_header: "排名,扫描频次,文件路径,风险等级,文件类型,完整路径",
+
+    pause_prompt: "\n>>> 按任意键退出程序 <<<",
+    status_analyzing: "🔍 正在分析日志文件: {}",
+    status_encoding: "🔤 检测到文本编码: {}",
+    status_merged_paths: "🧭 路径规范化: 合并了 {} 种重复写法",
+    status_duplicates: "🧹 多份日志有重叠: 丢弃了 {} 条重复条目",
+    status_report_written: "\n📄 报告已写入: {}",
+    status_csv_exported: "\n✅ 已导出高频扫描目标清单: {}\n   (UTF-8 BOM 格式，Excel/WPS 可直接正常打开中文)",
+    status_matrix_exported: "✅ 已导出进程交叉表: {}",
+    status_ingested: "🗄️ 已导入 {}: 新增 {} 条，跳过重复 {} 条",
+    status_history: "🗄️ 历史库 {} 现有 {} 条记录",
+    status_history_selected: "🗄️ 历史库 {}: 共 {} 条记录，符合条件 {} 条",
+
+    error_missing_file: "❌ 文件不存在: {}\n   使用方法: {} <文件路径> 或直接拖放文件到程序上",
+    error_invalid_format: "❌ 不是有效的火绒安全日志文件（需包含 '触犯自定义防护规则' 和 '操作文件：' 特征）: {}",
+    error_empty_log: "❌ 未检测到有效的 ACE 扫盘日志条目（文件: {}）",
+    error_invalid_snapshot: "❌ 无法读取 JSON 统计快照 {}: {}",
+    error_snapshot_version: "❌ JSON 报告 {} 的 schema 版本为 {}，当前版本为 {}，请用当前版本重新生成后再对比",
+    error_empty_history: "❌ 历史库中没有符合条件的 ACE 扫盘条目（目录: {}）",
+    error_categories_io: "无法读取分类规则文件: {}",
+    error_categories_toml: "分类规则文件格式错误: {}",
+    error_category_pattern: "分类「{}」的匹配规则无效: {}",
+    error_category_empty: "分类「{}」缺少匹配条件",
+    error_category_folder: "分类「{}」引用了未知的常用目录 `{}`（可选: {}）",
+};
+
+/// 英文
+pub static EN: Messages = Messages {
+    report_title: "🛡️ ACE Anti-Cheat Disk Scan Analysis Report",
+    report_subtitle: "(based on {} valid log entries)",
+    section_core: "📊 Key metrics",
+    section_processes: "🔍 Process activity",
+    section_top_files: "⚠️ Most scanned targets (Top {})",
+    section_categories: "📁 Target categories",
+    section_extensions: "🧩 File types",
+    section_operations: "🧮 Operation types",
+    section_process_matrix: "🔀 Process × target breakdown",
+    section_outcomes: "🚦 Operation results",
+    section_allowed_targets: "🕳️ Targets that slipped through ({} total)",
+    col_outcome: "Result",
+    col_allowed: "Allowed",
+    other_columns: "Other",
+    col_operation: "Operation",
+    section_time: "⏰ Scans by time of day",
+    section_timeline: "📅 Timeline",
+    section_heatmap: "🗓️ Date × hour",
+    section_sessions: "🎮 Game sessions ({} sessions)",
+    section_rules: "📜 Triggered rules ({} rules)",
+    section_tree: "🌲 Target directory tree",
+    section_hot_dirs: "🌲 Hottest directories ({} levels)",
+    section_history_trend: "📈 History trend (by week × machine; machines: {})",
+    section_recommendations: "🛡️ Hardening recommendations",
+
+    metric_total: "Total scan attempts",
+    metric_blocked: "Blocked attempts",
+    metric_block_rate: "Block rate",
+    note_block_rate: "block rate: {}",
+    metric_unique_files: "Unique target files",
+    metric_processes: "Active processes",
+    metric_duplicates: "Overlapping duplicates dropped",
+    metric_peak: "Peak hour",
+    peak_value: "{} ({} scans)",
+    metric_first_seen: "First seen",
+    metric_last_seen: "Last seen",
+    metric_span: "Time span",
+    metric: "Metric",
+    value: "Value",
+
+    col_rank: "Rank",
+    col_process: "Process",
+    col_scans: "Scans",
+    col_share: "Share",
+    col_risk: "Risk",
+    col_path: "File path",
+    col_hits: "Hits",
+    col_category: "Category",
+    col_extension: "Extension",
+    col_count: "Count",
+    col_name: "Name",
+    col_rule: "Rule",
+    col_start: "Start",
+    col_end: "End",
+    col_duration: "Duration",
+    col_files: "Files",
+    col_process_path: "Process path",
+    col_arguments: "Arguments",
+    col_dir: "Directory",
+    col_self: "Direct",
+    col_blocked: "Blocked",
+    col_top_target: "Top target",
+    col_hour: "Hour",
+    col_week: "Week",
+    col_machine: "Machine",
+    col_date: "Date",
+    col_distribution: "Distribution",
+    total: "Total",
+    no_extension: "(none)",
+    no_arguments: "(no arguments)",
+
+    heatmap_legend: "Legend: · none  ░ low  ▒ medium  ▓ high  █ peak",
+    tree_self: "(self {})",
+
+    risk_high: "High",
+    risk_medium: "Medium",
+    risk_low: "Low",
+
+    op_read: "Read",
+    op_query: "Query attributes",
+    op_enumerate: "Enumerate",
+    op_write: "Write",
+    op_delete: "Delete",
+    op_execute: "Execute",
+    op_other: "Other",
+
+    outcome_blocked: "Blocked",
+    outcome_allowed: "Allowed",
+    outcome_asked_allowed: "Asked, allowed",
+    outcome_asked_denied: "Asked, denied",
+    outcome_unknown: "Unknown",
+
+    duration_days: "{}d {}h {}m",
+    duration_hours: "{}h {}m",
+    duration_minutes: "{}m {}s",
+    duration_seconds: "{}s",
+
+    diff_title: "🔀 ACE Disk Scan Comparison",
+    section_diff_summary: "📊 Overall change",
+    section_new_targets: "🆕 New targets ({} total)",
+    section_gone_targets: "🚫 Targets no longer scanned ({} total)",
+    section_file_changes: "📈 Largest per-file frequency changes ({} files)",
+    section_category_changes: "📁 Category changes",
+    section_process_changes: "🔍 Process changes",
+    section_rule_changes: "📜 Rule changes",
+    col_before: "Before",
+    col_after: "After",
+    col_change: "Change",
+    col_status: "Status",
+    status_added: "new",
+    status_removed: "gone",
+    no_changes: "The two snapshots are identical",
+    percentage_points: "{} pp",
+
+    recommendations: [
+        "Driver layer: storage drivers (storqosflt.sys/storvsp.sys) are scanned heavily;\nconsider setting System32\\drivers to \"monitor only\" instead of \"block\"",
+        "Virtualization checks: components such as hvhostsvc.dll/vmms.exe are scanned,\nlikely to detect virtual machines; decide whether these paths should be allowed",
+        "Rule tuning: a 100% block rate may break game startup;\nconsider \"allow\" for the anti-cheat's own directory and \"ask\" for driver directories",
+    ],
+
+    csv_header: "Rank,Hits,File path,Risk level,File type,Full path",
+
+    pause_prompt: "\n>>> Press any key to exit <<<",
+    status_analyzing: "🔍 Analyzing log file: {}",
+    status_encoding: "🔤 Detected text encoding: {}",
+    status_merged_paths: "🧭 Path normalization: merged {} duplicate spellings",
+    status_duplicates: "🧹 Input logs overlap: dropped {} duplicate entries",
+    status_report_written: "\n📄 Report written to: {}",
+    status_csv_exported: "\n✅ Exported most scanned targets: {}\n   (UTF-8 with BOM, opens directly in Excel/WPS)",
+    status_matrix_exported: "✅ Exported process breakdown: {}",
+    status_ingested: "🗄️ Imported {}: {} new, {} duplicates skipped",
+    status_history: "🗄️ History store {} now holds {} entries",
+    status_history_selected: "🗄️ History store {}: {} entries, {} selected",
+
+    error_missing_file: "❌ File not found: {}\n   Usage: {} <log file>, or drag and drop the file onto the program",
+    error_invalid_format: "❌ Not a Huorong security log (expected '触犯自定义防护规则' and '操作文件：' markers): {}",
+    error_empty_log: "❌ No ACE disk scan entries found (file: {})",
+    error_invalid_snapshot: "❌ Cannot read JSON stats snapshot {}: {}",
+    error_snapshot_version: "❌ JSON report {} has schema version {}, but this version reads {}; regenerate it with the current version before comparing",
+    error_empty_history: "❌ No matching ACE disk scan entries in the history store (directory: {})",
+    error_categories_io: "Cannot read category rules file: {}",
+    error_categories_toml: "Invalid category rules file: {}",
+    error_category_pattern: "Invalid pattern in category \"{}\": {}",
+    error_category_empty: "Category \"{}\" has no match conditions",
+    error_category_folder: "Category \"{}\" references unknown folder `{}` (known: {})",
+};
@@ -0,0 +1,120 @@
+use std::collections::BTreeMap;
+
+use serde::{Deserialize, Serialize};
+
+use crate::history::HistoryTrend;
+use crate::matrix::CountMatrix;
+use crate::options::ReportOptions;
+use crate::session::ScanSession;
+use crate::stats::AceScanStats;
+
+/// JSON 报告的 schema 版本，字段出现不兼容变更时递增
+///
+/// - 2：`stats.target_categories` 的键改为稳定的分类 ID，显示名见 `category_labels`；
+///   无扩展名在 `stats.file_extensions` 中记为空字符串
+pub const JSON_SCHEMA_VERSION: u32 = 2;
+
+/// 机器可读的完整分析报告
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonReport {
+    pub schema_version: u32,
+    /// 生成报告的程序及版本，如 `fk-deltaforce 0.1.0`
+    pub generator: String,
+    /// 被分析的日志文件
+    #[serde(default)]
+    pub source: Option<String>,
+    /// 检测到的日志文本编码
+    #[serde(default)]
+    pub encoding: Option<String>,
+    /// 报告文字使用的语言代码
+    #[serde(default)]
+    pub locale: Option<String>,
+    /// 分类 ID 到显示名的映射
+    #[serde(default)]
+    pub category_labels: BTreeMap<String, String>,
+    pub summary: JsonSummary,
+    pub stats: AceScanStats,
+    /// 由 `stats` 生成的交叉表，与导出的 CSV 相同（键为分类 ID 与扩展名）
+    #[serde(default)]
+    pub matrices: JsonMatrices,
+    #[serde(default)]
+    pub sessions: Vec<ScanSession>,
+    /// 历史库报告中按周、按机器的趋势
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub trend: Option<HistoryTrend>,
+}
+
+/// 核心指标摘要（可由 `stats` 推出，便于直接读取）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct JsonSummary {
+    pub total_attempts: usize,
+    pub blocked_attempts: usize,
+    pub block_rate: f64,
+    pub unique_files: usize,
+    pub active_processes: usize,
+}
+
+/// 进程交叉表，行列均按合计降序排列
+#[derive(Debug, Clone, Default, Serialize, Deserialize)]
+pub struct JsonMatrices {
+    /// 进程 × 分类
+    pub process_categories: CountMatrix,
+    /// 进程 × 扩展名（无扩展名记为空字符串）
+    pub process_extensions: CountMatrix,
+}
+
+impl JsonReport {
+    pub fn new(stats: AceScanStats, sessions: Vec<ScanSession>) -> Self {
+        JsonReport {
+            schema_version: JSON_SCHEMA_VERSION,
+            generator: concat!(env!("CARGO_PKG_NAME"), " ", env!("CARGO_PKG_VERSION")).to_string(),
+            source: None,
+            encoding: None,
+            locale: None,
+            category_labels: BTreeMap::new(),
+            summary: JsonSummary {
+                total_attempts: stats.total_attempts,
+                blocked_attempts: stats.blocked_attempts,
+                block_rate: stats.block_rate(),
+                unique_files: stats.unique_files.len(),
+                active_processes: stats.processes.len(),
+            },
+            matrices: JsonMatrices {
+                process_categories: CountMatrix::from_nested(&stats.process_categories),
+                process_extensions: CountMatrix::from_nested(&stats.process_extensions),
+            },
+            stats,
+            sessions,
+            trend: None,
+        }
+    }
+
+    /// 记录日志来源与编码
+    pub fn with_source(mut self, source: impl Into<String>, encoding: impl Into<String>) -> Self {
+        self.source = Some(source.into());
+        self.encoding = Some(encoding.into());
+        self
+    }
+
+    /// 记录报告语言，并按 `options` 写入统计中出现的分类的显示名
+    pub fn with_labels(mut self, options: &ReportOptions) -> Self {
+        self.locale = Some(options.locale.code().to_string());
+        self.category_labels = self
+            .stats
+            .target_categories
+            .keys()
+            .map(|id| (id.clone(), options.category_label(id).to_string()))
+            .collect();
+        self
+    }
+
+    /// 序列化为带缩进的 JSON 文本
+    pub fn to_json_pretty(&self) -> serde_json::Result<String> {
+        serde_json::to_string_pretty(self)
+    }
+
+    /// 从 JSON 文本读取报告
+    pub fn from_json(text: &str) -> serde_json::Result<Self> {
+        serde_json::from_str(text)
+    }
+}
@@ -0,0 +1,107 @@
+//! 火绒安全日志中 ACE 反作弊（SGuard64 / SGuardSvc64）扫盘行为的解析与分析库。
+//!
+//! 处理流程分为几步，每一步都可以单独使用：
+//!
+//! - **读取**：[`LogFile`] 检测文本编码（UTF-8 / UTF-16 / GBK）与文件格式，
+//!   并通过 [`AceLogReader`] 流式逐条读取
+//! - **解析**：[`AceLogParser`] 将内存中的日志文本逐条解析为 [`AceLogEntry`]
+//! - **规范化**：[`PathNormalizer`] 将同一文件的不同写法（设备卷路径、8.3 短名、环境变量、
+//!   用户名目录、大小写）统一为一种，原文保留在条目的 `raw_*` 字段
+//! - **汇总**：[`AceScanStats`] 由条目聚合出进程、目标文件、分类、操作类型
+//!   （[`OperationKind`]）等统计，各项均按操作结果（[`OperationOutcome`]）细分；
+//!   多份导出日志相互重叠时先经 [`EntryDeduplicator`] 按条目身份（[`EntryKey`]）去重
+//! - **会话**：[`SessionDetector`] 按空闲间隔将日志切分为一次次游戏会话
+//! - **分类**：[`CategoryRules`] 按可配置的 TOML 规则对扫描目标归类，规则基于
+//!   [`WinPath`] 拆分出的盘符、常用目录、各级目录名与扩展名整段匹配，
+//!   内置规则见 [`DEFAULT_CATEGORY_RULES`]
+//! - **对比**：[`StatsDiff`] 对比两次统计（如游戏更新前后），找出新增与消失的目标、
+//!   各文件/分类/进程/规则的次数变化以及拦截率变化
+//! - **历史**：[`HistoryStore`] 将多次导入的日志条目去重后累积到本地历史库，
+//!   可按日期范围与机器（[`HistoryFilter`]）重新汇总，并按周、按机器汇总为 [`HistoryTrend`]
+//! - **渲染**：[`ReportModel`] 是与格式无关的报告数据模型，[`render_detailed_report`]、
+//!   [`render_markdown_report`] 与 [`render_html_report`] 分别将其排版为终端文本、
+//!   GitHub 风格 Markdown 和可离线分享的单文件 HTML，
+//!   Top-N 数量与风险阈值由 [`ReportOptions`] 控制
+//! - **本地化**：报告、导出与错误信息的文字来自 [`Messages`] 消息表，按 [`Locale`]
+//!   选择简体中文或英文；统计与导出中的分类使用稳定 ID，不随语言变化
+//! - **导出**：[`export_high_risk_targets`] 导出高频扫描目标 CSV，[`export_process_matrices`]
+//!   导出进程 × 分类、进程 × 扩展名交叉表（[`CountMatrix`]）CSV，
+//!   [`JsonReport`] 输出带 schema 版本的机器可读 JSON
+//!
+//! ```no_run
+//! use fk_deltaforce::{AceScanStats, LogFile, PathNormalizer, ReportOptions, render_detailed_report};
+//!
+//! let log = LogFile::open("fk-df.txt".as_ref())?;
+//! let mut normalizer = PathNormalizer::new();
+//! let mut stats = AceScanStats::default();
+//! for entry in log.entries() {
+//!     let mut entry = entry?;
+//!     normalizer.normalize_entry(&mut entry);
+//!     stats.record(&entry);
+//! }
+//! print!("{}", render_detailed_report(&stats, &[], &ReportOptions::default()));
+//! # Ok::<(), std::io::Error>(())
+//! ```
+
+mod category;
+mod dedup;
+mod diff;
+mod encoding;
+mod entry;
+mod export;
+mod history;
+mod html;
+mod i18n;
+mod json;
+mod markdown;
+mod matrix;
+mod model;
+mod normalize;
+mod operation;
+mod options;
+mod outcome;
+mod parser;
+mod reader;
+mod report;
+mod session;
+mod stats;
+mod tree;
+mod winpath;
+
+pub use category::{
+    categorize_target, CategoryError, CategoryRules, DEFAULT_CATEGORY_ID, DEFAULT_CATEGORY_RULES,
+};
+pub use diff::{CountChange, CountDiff, StatsDiff};
+pub use encoding::{detect_encoding, TextEncoding};
+pub use dedup::EntryDeduplicator;
+pub use entry::{AceLogEntry, EntryKey};
+pub use export::{
+    export_high_risk_targets, export_process_matrices, high_risk_targets_csv, process_category_csv,
+    process_extension_csv, HIGH_RISK_CSV, PROCESS_CATEGORY_CSV, PROCESS_EXTENSION_CSV,
+};
+pub use history::{
+    local_machine_name, machine_key, HistoryFilter, HistoryRecord, HistoryStore, HistoryTrend,
+    IngestSummary, TrendCounts, HISTORY_DIR, HISTORY_ENTRIES_FILE,
+};
+pub use html::{render_html, render_html_report, write_html};
+pub use i18n::{fill, Locale, Messages, EN, ZH_CN};
+pub use json::{JsonMatrices, JsonReport, JsonSummary, JSON_SCHEMA_VERSION};
+pub use markdown::{render_markdown, render_markdown_report, write_markdown};
+pub use matrix::CountMatrix;
+pub use model::{Block, Column, ColumnKind, Metric, ReportModel, Section, Table};
+pub use normalize::{PathNormalizer, VolumeMap, USER_PLACEHOLDER};
+pub use operation::OperationKind;
+pub use options::{ReportOptions, RiskLevel, RiskThresholds};
+pub use outcome::OperationOutcome;
+pub use parser::{parse_ace_logs_precise, AceLogParser, ENTRY_SEPARATOR};
+pub use reader::{is_huorong_log, AceLogReader, LogFile, SNIFF_LEN};
+pub use report::{
+    clamp_width, display_width, elide_middle, pad_to_width, render_detailed_report,
+    render_diff_report, render_rules_report, render_sessions_report, render_terminal, terminal_width,
+    write_detailed_report, write_diff_report, write_rules_report, write_sessions_report,
+    write_terminal, DEFAULT_WIDTH,
+};
+pub use session::{detect_sessions, ScanSession, SessionDetector, DEFAULT_SESSION_GAP};
+pub use stats::AceScanStats;
+pub use tree::{DirNode, DirTree};
+pub use winpath::{KnownFolder, WinPath};
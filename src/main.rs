@@ -1,402 +1,482 @@
-use std::collections::{HashMap, BTreeMap};
+mod cli;
+
 use std::fs;
-use std::path::PathBuf;
+use std::io;
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use chrono::TimeDelta;
+use clap::Parser;
 use console::Term;
 
-#[derive(Debug, Default)]
-struct AceScanStats {
-    total_attempts: usize,
-    blocked_attempts: usize,
-    unique_files: HashMap<String, usize>,
-    processes: HashMap<String, usize>,
-    rules_triggered: HashMap<String, usize>,
-    file_extensions: HashMap<String, usize>,
-    target_categories: HashMap<String, usize>,
-    time_distribution: BTreeMap<String, usize>,
+use cli::{Cli, Command, GlobalArgs, OutputFormat};
+use fk_deltaforce::{
+    export_high_risk_targets, export_process_matrices, fill, local_machine_name,
+    render_html, render_markdown, render_terminal, AceScanStats, CategoryError, CategoryRules,
+    EntryDeduplicator, HistoryStore, HistoryTrend, JsonReport, Locale, LogFile, PathNormalizer,
+    ReportModel, ReportOptions, ScanSession, SessionDetector, StatsDiff, TextEncoding,
+    DEFAULT_CATEGORY_RULES, HIGH_RISK_CSV, JSON_SCHEMA_VERSION,
+};
+
+type Result<T> = std::result::Result<T, AppError>;
+
+/// 程序错误，不同类别对应不同退出码
+#[derive(Debug)]
+enum AppError {
+    /// 日志文件不存在
+    MissingFile(PathBuf),
+    /// 不是火绒安全日志
+    InvalidFormat(PathBuf),
+    /// 日志中没有 ACE 扫盘条目
+    EmptyLog(PathBuf),
+    /// 分类规则文件无效
+    Categories(CategoryError),
+    /// JSON 统计快照无效
+    Snapshot(PathBuf, String),
+    /// JSON 报告的 schema 版本与当前版本不同
+    SnapshotVersion(PathBuf, u32),
+    /// 历史库中没有符合条件的条目
+    EmptyHistory(PathBuf),
+    /// 读写等其他错误
+    Io(io::Error),
 }
 
-fn main() -> Result<(), Box<dyn std::error::Error>> {
-    // 获取命令行参数
-    let args: Vec<String> = std::env::args().collect();
-    
-    // 确定日志文件路径：有参数用参数，否则用默认的 fk-df.txt
-    let log_path = if args.len() > 1 {
-        PathBuf::from(&args[1])
-    } else {
-        PathBuf::from("fk-df.txt")
-    };
-    
-    // 检查文件是否存在
-    if !log_path.exists() {
-        return Err(format!(
-            "❌ 文件不存在: {}\n   使用方法: {} <文件路径> 或直接拖放文件到程序上",
-            log_path.display(),
-            args.get(0).map(|s| s.as_str()).unwrap_or("程序名")
-        ).into());
+impl AppError {
+    fn exit_code(&self) -> ExitCode {
+        match self {
+            AppError::Io(_) => ExitCode::from(1),
+            AppError::MissingFile(_) => ExitCode::from(3),
+            AppError::InvalidFormat(_) => ExitCode::from(4),
+            AppError::EmptyLog(_) | AppError::EmptyHistory(_) => ExitCode::from(5),
+            AppError::Categories(_) => ExitCode::from(6),
+            AppError::Snapshot(..) | AppError::SnapshotVersion(..) => ExitCode::from(7),
+        }
     }
-    
-    // 验证是否为有效的火绒日志文件
-    if !is_huorong_log(&log_path)? {
-        return Err(format!(
-            "❌ 不是有效的火绒安全日志文件（需包含 'SGuard' 和 '操作文件：' 特征）: {}",
-            log_path.display()
-        ).into());
+}
+
+impl AppError {
+    /// 按 `locale` 生成错误信息
+    fn message(&self, locale: Locale) -> String {
+        let m = locale.messages();
+        match self {
+            AppError::MissingFile(path) => {
+                let program = std::env::args().next().unwrap_or_else(|| "fk-deltaforce".to_string());
+                fill(m.error_missing_file, &[&path.display(), &program])
+            }
+            AppError::InvalidFormat(path) => fill(m.error_invalid_format, &[&path.display()]),
+            AppError::EmptyLog(path) => fill(m.error_empty_log, &[&path.display()]),
+            AppError::Categories(e) => format!("❌ {}", e.localized(locale)),
+            AppError::Snapshot(path, e) => fill(m.error_invalid_snapshot, &[&path.display(), e]),
+            AppError::SnapshotVersion(path, version) => fill(
+                m.error_snapshot_version,
+                &[&path.display(), version, &JSON_SCHEMA_VERSION],
+            ),
+            AppError::EmptyHistory(path) => fill(m.error_empty_history, &[&path.display()]),
+            AppError::Io(e) => format!("❌ {}", e),
+        }
     }
-    
-    println!("🔍 正在分析日志文件: {}", log_path.display());
-    let contents = fs::read_to_string(&log_path)?;
-    let stats = parse_ace_logs_precise(&contents);
-    
-    if stats.total_attempts == 0 {
-        return Err(format!("❌ 未检测到有效的 ACE 扫盘日志条目（文件: {}）", log_path.display()).into());
+}
+
+impl From<io::Error> for AppError {
+    fn from(e: io::Error) -> Self {
+        AppError::Io(e)
     }
-    
-    generate_detailed_report(&stats);
-    export_high_risk_targets(&stats)?;
-    
-    println!("\n>>> 按任意键退出程序 <<<");
-    Term::stdout().read_char().unwrap();
-    Ok(())
 }
 
-/// 检测是否为火绒安全日志（快速特征检测）
-fn is_huorong_log(path: &PathBuf) -> Result<bool, Box<dyn std::error::Error>> {
-    let contents = fs::read_to_string(path)?;
-    let has_sguard = contents.contains("SGuard64") || contents.contains("SGuardSvc64");
-    let has_file_op = contents.contains("操作文件：");
-    Ok(has_sguard && has_file_op && contents.contains("触犯自定义防护规则"))
+/// 一次日志分析的全部结果
+struct Analysis {
+    sources: Vec<PathBuf>,
+    /// 各输入日志的文本编码一致时为该编码
+    encoding: Option<TextEncoding>,
+    stats: AceScanStats,
+    sessions: Vec<ScanSession>,
 }
 
-fn parse_ace_logs_precise(logs: &str) -> AceScanStats {
-    let mut stats = AceScanStats::default();
-    let entries: Vec<&str> = logs
-        .split(&">".repeat(60))
-        .filter(|e| !e.trim().is_empty() && e.contains("SGuard") && e.contains("操作文件："))
-        .collect();
+impl Analysis {
+    /// 输入日志的名称，多份时以逗号分隔
+    fn source_names(&self) -> String {
+        self.sources
+            .iter()
+            .map(|p| p.display().to_string())
+            .collect::<Vec<_>>()
+            .join(", ")
+    }
+}
 
-    for entry in entries {
-        stats.total_attempts += 1;
+fn main() -> ExitCode {
+    let cli = Cli::parse();
+    let pause = should_pause(&cli);
+    let locale = cli.global.locale();
 
-        if let Some(file_path) = extract_field(entry, "操作文件：", &["操作结果：", "操作类型：", "\r\n", "\n"]) {
-            let file_path = file_path.trim().to_string();
-            if !file_path.is_empty() {
-                *stats.unique_files.entry(file_path.clone()).or_insert(0) += 1;
+    let code = match run(cli) {
+        Ok(()) => ExitCode::SUCCESS,
+        Err(e) => {
+            eprintln!("{}", e.message(locale));
+            e.exit_code()
+        }
+    };
 
-                let ext = file_path
-                    .rsplit('.')
-                    .next()
-                    .map(|s| s.to_lowercase())
-                    .unwrap_or_else(|| "无扩展名".to_string());
-                *stats.file_extensions.entry(ext).or_insert(0) += 1;
+    if pause {
+        println!("{}", locale.messages().pause_prompt);
+        let _ = Term::stdout().read_char();
+    }
+    code
+}
 
-                categorize_target(&file_path, &mut stats.target_categories);
+/// 仅在拖放/双击启动（无子命令、无选项，只有任意个日志文件）且连接终端时暂停，避免窗口一闪而过
+fn should_pause(cli: &Cli) -> bool {
+    if cli.global.pause {
+        return true;
+    }
+    if cli.global.no_pause || cli.command.is_some() {
+        return false;
+    }
+    // 拖放到程序上的文件以完整路径传入，不会以 `-` 开头
+    let has_flags = std::env::args_os()
+        .skip(1)
+        .any(|arg| arg.to_string_lossy().starts_with('-'));
+    !has_flags && Term::stdout().is_term()
+}
+
+fn run(cli: Cli) -> Result<()> {
+    let global = cli.global;
+    let custom_rules = match &global.categories {
+        Some(path) => Some(CategoryRules::load(path).map_err(AppError::Categories)?),
+        None => None,
+    };
+    let rules = custom_rules.as_ref().unwrap_or_else(|| CategoryRules::builtin());
+
+    // 未指定子命令（含拖放文件到程序上）时生成完整报告
+    match cli.command.unwrap_or(Command::Report(cli.report)) {
+        Command::Report(args) => {
+            let analysis = analyze(&args.input.logs, Some(args.session.gap()), rules, &global)?;
+            let options = global.report_options(args.options(), rules);
+            let report = full_report(
+                &analysis.stats,
+                &analysis.sessions,
+                None,
+                &options,
+                &global,
+                analysis.source_names(),
+                analysis.encoding,
+            )?;
+            emit(&report, &global)?;
+            if !args.no_export {
+                export_csv(&analysis.stats, &options, &global)?;
             }
         }
-
-        if let Some(proc_path) = extract_field(entry, "操作进程：", &["操作进程命令行：", "操作类型：", "\r\n", "\n"]) {
-            let proc_name = proc_path
-                .split('\\')
-                .last()
-                .map(|s| s.trim().to_string())
-                .unwrap_or_else(|| "unknown".to_string());
-            *stats.processes.entry(proc_name).or_insert(0) += 1;
+        Command::Export(args) => {
+            let analysis = analyze(&args.input.logs, None, rules, &global)?;
+            let options = global.report_options(args.options(), rules);
+            export_csv(&analysis.stats, &options, &global)?;
         }
-
-        if let Some(rule_name) = extract_field(entry, "触犯规则：", &["操作类型：", "\r\n", "\n"]) {
-            let rule = rule_name.trim().to_string();
-            if !rule.is_empty() {
-                *stats.rules_triggered.entry(rule).or_insert(0) += 1;
-            }
+        Command::Sessions(args) => {
+            let analysis = analyze(&args.input.logs, Some(args.session.gap()), rules, &global)?;
+            let options = global.report_options(ReportOptions::default(), rules);
+            let report = match global.format {
+                OutputFormat::Json => {
+                    serde_json::to_string_pretty(&analysis.sessions).map_err(io::Error::from)?
+                }
+                _ => {
+                    let model = ReportModel::sessions(&analysis.sessions, &options);
+                    render_model(&model, &options, &global)
+                }
+            };
+            emit(&report, &global)?;
         }
-
-        if entry.contains("操作结果：已阻止") {
-            stats.blocked_attempts += 1;
+        Command::Rules(args) => {
+            let analysis = analyze(&args.logs, None, rules, &global)?;
+            let options = global.report_options(ReportOptions::default(), rules);
+            let report = match global.format {
+                OutputFormat::Json => serde_json::to_string_pretty(&analysis.stats.rules_triggered)
+                    .map_err(io::Error::from)?,
+                _ => {
+                    let model = ReportModel::rules(&analysis.stats, &options);
+                    render_model(&model, &options, &global)
+                }
+            };
+            emit(&report, &global)?;
         }
-
-        if let Some(hour) = extract_hour(entry) {
-            let hour_key = format!("{:02}:00-{:02}:59", hour, hour);
-            *stats.time_distribution.entry(hour_key).or_insert(0) += 1;
+        Command::Categories(args) => {
+            if args.paths.is_empty() {
+                print!("{}", DEFAULT_CATEGORY_RULES);
+            }
+            let locale = global.locale();
+            let mut normalizer = global.normalizer();
+            for path in &args.paths {
+                let path = normalizer.normalize(path);
+                let id = rules.categorize(&path);
+                let label = rules.label(id, locale).unwrap_or(id);
+                println!("{}\t{}\t{}", id, label, path);
+            }
         }
-    }
-
-    stats
-}
-
-fn extract_field<'a>(text: &'a str, prefix: &str, terminators: &[&str]) -> Option<&'a str> {
-    text.find(prefix).and_then(|start| {
-        let value_start = start + prefix.len();
-        if value_start >= text.len() {
-            return None;
+        Command::Diff(args) => {
+            let before = load_stats(&args.before, rules, &global)?;
+            let after = load_stats(&args.after, rules, &global)?;
+            let diff = StatsDiff::between(&before, &after);
+            let options = global.report_options(
+                ReportOptions { top_files: args.top, ..ReportOptions::default() },
+                rules,
+            );
+            let before_name = args.before.display().to_string();
+            let after_name = args.after.display().to_string();
+            let report = match global.format {
+                OutputFormat::Json => {
+                    serde_json::to_string_pretty(&diff).map_err(io::Error::from)?
+                }
+                _ => {
+                    let model = ReportModel::diff(&diff, &before_name, &after_name, &options);
+                    render_model(&model, &options, &global)
+                }
+            };
+            emit(&report, &global)?;
         }
-        
-        let value_end = terminators
-            .iter()
-            .filter_map(|term| text[value_start..].find(term))
-            .min()
-            .map(|pos| value_start + pos)
-            .unwrap_or(text.len());
-        
-        if value_start >= value_end {
-            None
-        } else {
-            Some(&text[value_start..value_end])
+        Command::Ingest(args) => {
+            let mut store = HistoryStore::open(&global.history_dir())?;
+            let machine = args.machine.unwrap_or_else(local_machine_name);
+            let m = global.locale().messages();
+            let mut total = 0;
+            for log_path in &args.logs {
+                let log = open_log(log_path, &global)?;
+                let mut normalizer = global.normalizer();
+                let entries = log.entries().map(|entry| {
+                    entry.map(|mut entry| {
+                        normalizer.normalize_entry(&mut entry);
+                        entry
+                    })
+                });
+                let source = log_path.display().to_string();
+                let summary = store.ingest(&machine, &source, entries)?;
+                if summary.added + summary.duplicates == 0 {
+                    return Err(AppError::EmptyLog(log_path.to_path_buf()));
+                }
+                report_merged_paths(&normalizer, &global);
+                status(
+                    &global,
+                    &fill(m.status_ingested, &[&source, &summary.added, &summary.duplicates]),
+                );
+                total = summary.total;
+            }
+            status(&global, &fill(m.status_history, &[&store.dir().display(), &total]));
         }
-    })
-}
+        Command::History(args) => {
+            let history_dir = global.history_dir();
+            let store = HistoryStore::open(&history_dir)?;
+            let filter = args.filter();
+            let mut stats = AceScanStats::default();
+            let mut sessions = SessionDetector::new(args.session.gap());
+            let mut trend = HistoryTrend::default();
+            let mut total = 0;
+            for record in store.records()? {
+                let record = record?;
+                total += 1;
+                if filter.matches(&record) {
+                    stats.record_with(&record.entry, rules);
+                    sessions.record(&record.entry);
+                    trend.record(&record);
+                }
+            }
+            let m = global.locale().messages();
+            status(
+                &global,
+                &fill(m.status_history_selected, &[&history_dir.display(), &total, &stats.total_attempts]),
+            );
+            if stats.total_attempts == 0 {
+                return Err(AppError::EmptyHistory(history_dir));
+            }
 
-fn extract_hour(entry: &str) -> Option<u32> {
-    entry
-        .lines()
-        .next()
-        .and_then(|first_line| {
-            first_line
-                .split_whitespace()
-                .nth(1)
-                .and_then(|time_part| time_part.split(':').next())
-                .and_then(|hour_str| hour_str.parse::<u32>().ok())
-        })
-        .filter(|&h| h < 24)
+            let options = global.report_options(args.display.options(), rules);
+            let report = full_report(
+                &stats,
+                &sessions.finish(),
+                Some(&trend),
+                &options,
+                &global,
+                history_dir.display().to_string(),
+                None,
+            )?;
+            emit(&report, &global)?;
+        }
+    }
+    Ok(())
 }
 
-fn categorize_target(file_path: &str, categories: &mut HashMap<String, usize>) {
-    let lower_path = file_path.to_lowercase();
-
-    let category = if lower_path.contains("system32\\drivers") || lower_path.contains("syswow64\\drivers") {
-        "系统驱动"
-    } else if lower_path.contains("system32") {
-        "System32核心"
-    } else if lower_path.contains("syswow64") {
-        "SysWOW64(32位)"
-    } else if lower_path.contains("microsoft.net") || lower_path.contains("dotnet") {
-        ".NET组件"
-    } else if lower_path.contains("anti cheat expert") 
-        || lower_path.contains("sguard") 
-        || lower_path.contains("ace") 
-        || lower_path.contains("eac") {
-        "反作弊组件"
-    } else if lower_path.contains("windows\\systemapps") || lower_path.contains("windowsapps") {
-        "WindowsApps"
-    } else if lower_path.contains("programdata") || lower_path.contains("appdata") {
-        "用户数据目录"
-    } else if lower_path.contains("windows\\winsxs") {
-        "WinSxS组件存储"
-    } else {
-        "其他系统文件"
+/// 按输出格式生成完整分析报告，给出 `trend` 时为历史库报告；`source` 与 `encoding` 记录在 JSON 报告中
+fn full_report(
+    stats: &AceScanStats,
+    sessions: &[ScanSession],
+    trend: Option<&HistoryTrend>,
+    options: &ReportOptions,
+    global: &GlobalArgs,
+    source: String,
+    encoding: Option<TextEncoding>,
+) -> Result<String> {
+    if global.format == OutputFormat::Json {
+        let mut report = JsonReport::new(stats.clone(), sessions.to_vec()).with_labels(options);
+        report.source = Some(source);
+        report.encoding = encoding.map(|e| e.name().to_string());
+        report.trend = trend.cloned();
+        return Ok(report.to_json_pretty().map_err(io::Error::from)?);
+    }
+    let model = match trend {
+        Some(trend) => ReportModel::history(stats, sessions, trend, options),
+        None => ReportModel::detailed(stats, sessions, options),
     };
-
-    *categories.entry(category.to_string()).or_insert(0) += 1;
+    Ok(render_model(&model, options, global))
 }
 
-/// 计算字符串在等宽终端中的显示宽度（中文字符占2，英文占1）
-fn display_width(s: &str) -> usize {
-    s.chars().map(|c| {
-        if c.len_utf8() > 1 {
-            2 // 中文、emoji等宽字符
-        } else {
-            1 // ASCII字符
-        }
-    }).sum()
+/// 按输出格式排版报告模型；JSON 报告由各命令直接序列化数据生成，不经过报告模型
+fn render_model(model: &ReportModel, options: &ReportOptions, global: &GlobalArgs) -> String {
+    match global.format {
+        OutputFormat::Text | OutputFormat::Json => render_terminal(model, options.width),
+        OutputFormat::Markdown => render_markdown(model),
+        OutputFormat::Html => render_html(model),
+    }
 }
 
-/// 截断或填充字符串到指定显示宽度
-fn pad_to_width(s: &str, width: usize) -> String {
-    let current_width = display_width(s);
-    if current_width >= width {
-        // 需要截断
-        let mut result = String::new();
-        let mut current = 0;
-        for c in s.chars() {
-            let w = if c.len_utf8() > 1 { 2 } else { 1 };
-            if current + w > width - 1 {
-                result.push('…');
-                break;
-            }
-            result.push(c);
-            current += w;
+/// 输出报告：写入 `--report-file` 指定的文件，否则打印到标准输出
+fn emit(report: &str, global: &GlobalArgs) -> Result<()> {
+    match &global.report_file {
+        Some(path) => {
+            fs::write(path, report)?;
+            let m = global.locale().messages();
+            status(global, &fill(m.status_report_written, &[&path.display()]));
         }
-        result
-    } else {
-        // 填充空格
-        format!("{}{}", s, " ".repeat(width - current_width))
+        None if report.ends_with('\n') => print!("{}", report),
+        None => println!("{}", report),
     }
+    Ok(())
 }
 
-fn generate_detailed_report(stats: &AceScanStats) {
-    const WIDTH: usize = 76;
-    println!("\n{}", "=".repeat(WIDTH));
-    println!("{:^WIDTH$}", "🛡️ ACE反作弊系统扫盘行为深度分析报告");
-    println!("{:^WIDTH$}", format!("(基于 {} 条有效日志条目)", stats.total_attempts));
-    println!("{}", "=".repeat(WIDTH));
-
-    println!("\n「📊 核心指标」");
-    println!("  • 总扫盘尝试次数: {:>10}", stats.total_attempts);
-    let block_rate = if stats.total_attempts > 0 {
-        stats.blocked_attempts as f64 / stats.total_attempts as f64 * 100.0
+/// 打印进度提示；机器可读格式输出到标准输出时改写到标准错误，避免污染报告
+fn status(global: &GlobalArgs, message: &str) {
+    if global.quiet {
+        return;
+    }
+    if global.format == OutputFormat::Text || global.report_file.is_some() {
+        println!("{}", message);
     } else {
-        0.0
-    };
-    println!("  • 成功阻止次数:   {:>10} (拦截率: {:.1}%)", stats.blocked_attempts, block_rate);
-    println!("  • 唯一目标文件数: {:>10}", stats.unique_files.len());
-    println!("  • 活跃进程数:     {:>10}", stats.processes.len());
-
-    println!("\n「🔍 进程行为分析」");
-    let mut procs: Vec<_> = stats.processes.iter().collect();
-    procs.sort_by(|a, b| b.1.cmp(a.1));
-    for (i, (proc, count)) in procs.iter().take(5).enumerate() {
-        let risk_level: &str = if **count > 500 {
-            "🔴 高危"
-        } else if **count > 200 {
-            "🟠 中危"
-        } else {
-            "🟢 低危"
-        };
-        println!("  {:2}. {:28} {:>8} 次  {}", i + 1, proc, count, risk_level);
+        eprintln!("{}", message);
     }
+}
 
-    // 修复对齐：统一使用固定宽度
-    println!("\n「⚠️ 高频扫描目标 (Top 15)」");
-    println!("  {:>4}  {:<50} {:>8}  {}", "排名", "文件路径", "频次", "风险");
-    println!("  {}", "-".repeat(74));
-
-    let mut files: Vec<_> = stats.unique_files.iter().collect();
-    files.sort_by(|a, b| b.1.cmp(a.1));
-
-    for (i, (file, count)) in files.iter().take(15).enumerate() {
-        let risk: &str = if **count > 30 {
-            "🔴"
-        } else if **count > 10 {
-            "🟠"
-        } else {
-            "🟢"
-        };
-        
-        // 处理文件路径显示：截断中间部分
-        let display_path = if display_width(file) > 50 {
-            let total_chars = file.chars().count();
-            let prefix_len = 20;
-            let suffix_len = 26;
-            let prefix: String = file.chars().take(prefix_len).collect();
-            let suffix: String = file.chars().skip(total_chars.saturating_sub(suffix_len)).collect();
-            format!("{}...{}", prefix, suffix)
-        } else {
-            file.to_string()
-        };
-        
-        // 使用 pad_to_width 确保严格对齐
-        let padded_path = pad_to_width(&display_path, 50);
-        println!("  {:>3}. {} {:>8}  {}", i + 1, padded_path, count, risk);
+/// 校验日志文件是否存在且为火绒日志，并提示编码
+fn open_log(log_path: &Path, global: &GlobalArgs) -> Result<LogFile> {
+    // 检查文件是否存在
+    if !log_path.exists() {
+        return Err(AppError::MissingFile(log_path.to_path_buf()));
     }
-
-    // 修复格式对齐：使用 display_width 计算中文字符宽度进行补偿
-    println!("\n「📁 扫描目标分类统计」");
-    println!("  {:<20} {:>12} {:>12}  {}", "分类", "扫描次数", "占比", "风险");
-    println!("  {}", "-".repeat(74));
-    
-    let mut cats: Vec<_> = stats.target_categories.iter().collect();
-    cats.sort_by(|a, b| b.1.cmp(a.1));
     
-    for (cat, count) in &cats {
-        let count_val = **count;
-        let percent = count_val as f64 / stats.total_attempts as f64 * 100.0;
-        let risk_icon: &str = if count_val > 1000 {
-            "🔴"
-        } else if count_val > 300 {
-            "🟠"
-        } else {
-            "🟢"
-        };
-        
-        // 计算需要填充的空格数，确保对齐
-        let cat_width = display_width(cat);
-        let target_width = 20usize;
-        let padding = if cat_width < target_width {
-            target_width - cat_width
-        } else {
-            0
-        };
-        
-        println!(
-            "  {}{:padding$} {:>10} 次 ({:>6.1}%)  {}",
-            cat, "", count_val, percent, risk_icon
-        );
+    // 验证是否为有效的火绒日志文件（只检查文件头部）
+    let log = LogFile::open(log_path)?;
+    if !log.is_huorong_log() {
+        return Err(AppError::InvalidFormat(log_path.to_path_buf()));
     }
+    
+    let m = global.locale().messages();
+    status(global, &fill(m.status_analyzing, &[&log_path.display()]));
+    status(global, &fill(m.status_encoding, &[&log.encoding()]));
+    Ok(log)
+}
 
-    println!("\n「🧩 文件类型分布」");
-    let mut exts: Vec<_> = stats.file_extensions.iter().collect();
-    exts.sort_by(|a, b| b.1.cmp(a.1));
-    for (ext, count) in exts.iter().take(8) {
-        let count_val = **count;
-        let percent = count_val as f64 / stats.total_attempts as f64 * 100.0;
-        println!("  .{:6} {:>8} 次 ({:>6.1}%)", ext, count_val, percent);
-    }
-
-    if !stats.time_distribution.is_empty() {
-        println!("\n「⏰ 扫描行为时间分布」");
-        let mut times: Vec<_> = stats.time_distribution.iter().collect();
-        times.sort_by_key(|(k, _)| *k);
-
-        let peak_count = times.iter().map(|(_, v)| **v).max().unwrap_or(1);
-        let peak_time = times.iter().max_by_key(|(_, v)| **v).map(|(t, _)| t.as_str()).unwrap_or("");
-        println!("  扫描高峰: {} (共 {} 次)", peak_time, peak_count);
+/// 校验并流式解析日志文件；多份日志相互重叠时按条目身份去重
+///
+/// 会话切分需要缓存每条日志的时间与目标，只在 `session_gap` 给出时进行，
+/// 不显示会话的命令传 `None` 以保持内存占用与日志大小无关。
+fn analyze(
+    log_paths: &[PathBuf],
+    session_gap: Option<TimeDelta>,
+    rules: &CategoryRules,
+    global: &GlobalArgs,
+) -> Result<Analysis> {
+    let mut encodings = Vec::new();
+    let mut normalizer = global.normalizer();
+    let mut dedup = EntryDeduplicator::new();
+    let mut stats = AceScanStats::default();
+    let mut sessions = session_gap.map(SessionDetector::new);
+    for (i, log_path) in log_paths.iter().enumerate() {
+        let log = open_log(log_path, global)?;
+        encodings.push(log.encoding());
+        if i + 1 == log_paths.len() {
+            dedup.last_input();
+        }
+        let before = stats.total_attempts + dedup.duplicates();
+        for entry in log.entries() {
+            let mut entry = entry?;
+            normalizer.normalize_entry(&mut entry);
+            if dedup.insert(&entry) {
+                stats.record_with(&entry, rules);
+                if let Some(sessions) = &mut sessions {
+                    sessions.record(&entry);
+                }
+            }
+        }
+        dedup.next_input();
 
-        for (time, count) in times.iter().take(12) {
-            let count_val = **count;
-            let bar_width = (count_val as f64 / peak_count as f64 * 40.0).round() as usize;
-            let bar = "█".repeat(bar_width);
-            println!("  {} {:>6} {}", time, count_val, bar);
+        if stats.total_attempts + dedup.duplicates() == before {
+            return Err(AppError::EmptyLog(log_path.to_path_buf()));
         }
     }
+    
+    report_merged_paths(&normalizer, global);
+    stats.duplicate_entries = dedup.duplicates();
+    if stats.duplicate_entries > 0 {
+        let m = global.locale().messages();
+        status(global, &fill(m.status_duplicates, &[&stats.duplicate_entries]));
+    }
 
-    println!("\n「🛡️ 安全加固建议」");
-    println!("  1️⃣  驱动层防护：存储驱动(storqosflt.sys/storvsp.sys)被高频扫描，");
-    println!("      建议对 System32\\drivers 目录设置「仅监控」而非「阻止」");
-    println!("  2️⃣  虚拟化检测：hvhostsvc.dll/vmms.exe 等组件被扫描，");
-    println!("      可能用于检测虚拟机环境，评估是否需放行相关路径");
-    println!("  3️⃣  规则优化：100%拦截率可能导致游戏启动异常，");
-    println!("      建议对反作弊组件自身目录设置「放行」，对驱动目录设置「询问」");
-
-    println!("\n{}", "=".repeat(WIDTH));
+    let encoding = encodings.first().copied().filter(|e| encodings.iter().all(|x| x == e));
+    Ok(Analysis {
+        sources: log_paths.to_vec(),
+        encoding,
+        stats,
+        sessions: sessions.map(SessionDetector::finish).unwrap_or_default(),
+    })
 }
 
-fn export_high_risk_targets(stats: &AceScanStats) -> Result<(), Box<dyn std::error::Error>> {
-    let mut files: Vec<_> = stats.unique_files.iter().collect();
-    files.sort_by(|a, b| b.1.cmp(a.1));
-
-    let mut csv = String::from("排名,扫描频次,文件路径,风险等级,文件类型,完整路径\n");
-
-    for (i, (file, count)) in files.iter().enumerate().take(200) {
-        let count_val = **count;
-        let risk: &str = if count_val > 30 {
-            "高危"
-        } else if count_val > 10 {
-            "中危"
-        } else {
-            "低危"
-        };
-        let ext = file
-            .rsplit('.')
-            .next()
-            .unwrap_or("无")
-            .to_string();
-
-        let safe_file = if file.contains(',') || file.contains('\n') || file.contains('\"') {
-            format!("\"{}\"", file.replace('\"', "\"\""))
-        } else {
-            file.to_string()
-        };
-        
-        // 添加完整路径列（方便直接复制到火绒规则）
-        csv.push_str(&format!("{},{},{},{},{},\"{}\"\n", i + 1, count_val, safe_file, risk, ext, file));
+fn report_merged_paths(normalizer: &PathNormalizer, global: &GlobalArgs) {
+    if normalizer.merged_targets() > 0 {
+        let m = global.locale().messages();
+        status(global, &fill(m.status_merged_paths, &[&normalizer.merged_targets()]));
     }
+}
 
-    // 添加UTF-8 BOM解决Excel乱码
-    let mut bom_csv = Vec::from(&[0xEFu8, 0xBB, 0xBF][..]);
-    bom_csv.extend_from_slice(csv.as_bytes());
-    
-    fs::write("high_risk_targets.csv", bom_csv)?;
-    
-    println!("\n✅ 已导出高频扫描目标清单: high_risk_targets.csv");
-    println!("   (UTF-8 BOM 格式，Excel/WPS 可直接正常打开中文)");
+/// 读取对比输入：`.json` 文件按 `-f json` 报告（或单独的 `stats` 对象）解析，其余按日志分析
+fn load_stats(path: &Path, rules: &CategoryRules, global: &GlobalArgs) -> Result<AceScanStats> {
+    let is_json = path
+        .extension()
+        .is_some_and(|ext| ext.eq_ignore_ascii_case("json"));
+    if !is_json {
+        return Ok(analyze(&[path.to_path_buf()], None, rules, global)?.stats);
+    }
+    if !path.exists() {
+        return Err(AppError::MissingFile(path.to_path_buf()));
+    }
+    let text = fs::read_to_string(path)?;
+    match JsonReport::from_json(&text) {
+        // 各版本的统计键含义不同（如 1 版以显示名作分类键），直接对比会得出错误的增减
+        Ok(report) if report.schema_version != JSON_SCHEMA_VERSION => Err(
+            AppError::SnapshotVersion(path.to_path_buf(), report.schema_version),
+        ),
+        Ok(report) => Ok(report.stats),
+        // 单独的 `stats` 对象各字段均有默认值，需带有 `unique_files` 才视为统计快照
+        Err(e) => serde_json::from_str::<serde_json::Value>(&text)
+            .ok()
+            .filter(|value| value.get("unique_files").is_some())
+            .and_then(|value| serde_json::from_value::<AceScanStats>(value).ok())
+            .ok_or_else(|| AppError::Snapshot(path.to_path_buf(), e.to_string())),
+    }
+}
 
+/// 将高频扫描目标清单与进程交叉表导出到输出目录
+fn export_csv(stats: &AceScanStats, options: &ReportOptions, global: &GlobalArgs) -> Result<()> {
+    std::fs::create_dir_all(&global.output_dir)?;
+    let csv_path = global.output_dir.join(HIGH_RISK_CSV);
+    export_high_risk_targets(stats, options, &csv_path)?;
+    let m = global.locale().messages();
+    status(global, &fill(m.status_csv_exported, &[&csv_path.display()]));
+    for path in export_process_matrices(stats, options, &global.output_dir)? {
+        status(global, &fill(m.status_matrix_exported, &[&path.display()]));
+    }
     Ok(())
-}
\ No newline at end of file
+}
@@ -0,0 +1,162 @@
+use std::fmt::{self, Write};
+
+use crate::i18n::Messages;
+use crate::model::{Block, ColumnKind, Metric, ReportModel, Table};
+use crate::options::ReportOptions;
+use crate::report::{display_width, heat_cell, hour_axis, pad_left, pad_to_width};
+use crate::session::ScanSession;
+use crate::stats::AceScanStats;
+
+/// Markdown 柱状图最长柱宽
+const BAR_WIDTH: usize = 20;
+
+/// 生成 GitHub 风格 Markdown 版详细分析报告（与终端报告同一数据模型）
+pub fn render_markdown_report(
+    stats: &AceScanStats,
+    sessions: &[ScanSession],
+    options: &ReportOptions,
+) -> String {
+    render_markdown(&ReportModel::detailed(stats, sessions, options))
+}
+
+/// 将任意报告模型渲染为 Markdown
+pub fn render_markdown(model: &ReportModel) -> String {
+    let mut out = String::new();
+    write_markdown(&mut out, model).expect("写入 String 不会失败");
+    out
+}
+
+/// 以 Markdown 排版输出报告模型
+pub fn write_markdown<W: Write>(out: &mut W, model: &ReportModel) -> fmt::Result {
+    let m = model.locale.messages();
+    if let Some(title) = &model.title {
+        writeln!(out, "# {}\n", title)?;
+        if let Some(subtitle) = &model.subtitle {
+            writeln!(out, "> {}\n", subtitle)?;
+        }
+    }
+
+    for section in &model.sections {
+        writeln!(out, "## {}\n", section.title)?;
+        for block in &section.blocks {
+            match block {
+                Block::Metrics(metrics) => write_metrics(out, metrics, m)?,
+                Block::Table(table) => write_table(out, table)?,
+                Block::Bars { header, bars } => write_bars(out, header, bars, m)?,
+                Block::Heatmap(rows) => write_heatmap(out, rows, m)?,
+                Block::List(items) => write_list(out, items)?,
+                Block::Tree(_) => continue,
+            }
+            writeln!(out)?;
+        }
+    }
+    Ok(())
+}
+
+fn write_metrics<W: Write>(out: &mut W, metrics: &[Metric], m: &Messages) -> fmt::Result {
+    writeln!(out, "| {} | {} |\n| --- | ---: |", escape(m.metric), escape(m.value))?;
+    for metric in metrics {
+        match &metric.note {
+            Some(note) => writeln!(
+                out,
+                "| {} | {} ({}) |",
+                escape(&metric.label),
+                escape(&metric.value),
+                escape(note)
+            )?,
+            None => writeln!(out, "| {} | {} |", escape(&metric.label), escape(&metric.value))?,
+        }
+    }
+    Ok(())
+}
+
+fn write_table<W: Write>(out: &mut W, table: &Table) -> fmt::Result {
+    let headers: Vec<_> = table.columns.iter().map(|c| escape(&c.header)).collect();
+    writeln!(out, "| {} |", headers.join(" | "))?;
+    let aligns: Vec<_> = table
+        .columns
+        .iter()
+        .map(|c| match c.kind {
+            ColumnKind::Number => "---:",
+            ColumnKind::Text | ColumnKind::Path => "---",
+        })
+        .collect();
+    writeln!(out, "| {} |", aligns.join(" | "))?;
+
+    for row in table.all_rows() {
+        let cells: Vec<_> = row
+            .iter()
+            .zip(&table.columns)
+            .map(|(cell, col)| match col.kind {
+                // 路径放进代码片段，避免反斜杠被当作转义符
+                ColumnKind::Path if !cell.is_empty() => format!("`{}`", cell.replace('|', "\\|")),
+                _ => escape(cell),
+            })
+            .collect();
+        writeln!(out, "| {} |", cells.join(" | "))?;
+    }
+    Ok(())
+}
+
+fn write_bars<W: Write>(
+    out: &mut W,
+    header: &str,
+    bars: &[(String, usize)],
+    m: &Messages,
+) -> fmt::Result {
+    let peak = bars.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1);
+    writeln!(
+        out,
+        "| {} | {} | {} |\n| --- | ---: | --- |",
+        escape(header),
+        escape(m.col_count),
+        escape(m.col_distribution)
+    )?;
+    for (label, count) in bars {
+        let bar_width = (*count as f64 / peak as f64 * BAR_WIDTH as f64).round() as usize;
+        writeln!(out, "| {} | {} | {} |", escape(label), count, "█".repeat(bar_width))?;
+    }
+    Ok(())
+}
+
+fn write_heatmap<W: Write>(
+    out: &mut W,
+    rows: &[(String, [usize; 24])],
+    m: &Messages,
+) -> fmt::Result {
+    let label_width = rows.iter().map(|(l, _)| display_width(l)).max().unwrap_or(0);
+    let peak = rows
+        .iter()
+        .flat_map(|(_, hours)| hours.iter().copied())
+        .max()
+        .unwrap_or(1);
+    writeln!(out, "```text")?;
+    writeln!(out, "{}  {}  {}", " ".repeat(label_width), hour_axis(), pad_left(m.total, 6))?;
+    for (label, hours) in rows {
+        let cells: String = hours.iter().map(|&c| heat_cell(c, peak)).collect();
+        let total: usize = hours.iter().sum();
+        writeln!(out, "{}  {}  {:>6}", pad_to_width(label, label_width), cells, total)?;
+    }
+    writeln!(out, "```")?;
+    writeln!(out, "\n{}", m.heatmap_legend)
+}
+
+fn write_list<W: Write>(out: &mut W, items: &[String]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        let lines: Vec<_> = item.lines().map(escape).collect();
+        writeln!(out, "{}. {}", i + 1, lines.join("<br>"))?;
+    }
+    Ok(())
+}
+
+/// 转义会破坏表格或被误解析的 Markdown 字符
+fn escape(s: &str) -> String {
+    let mut escaped = String::with_capacity(s.len());
+    for c in s.chars() {
+        if matches!(c, '|' | '\\' | '*' | '_' | '`' | '<' | '>' | '[' | ']') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+
+use serde::{Deserialize, Serialize};
+
+/// 二维计数表（如进程 × 分类），行与列均按合计降序（相同时按名称）排列
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct CountMatrix {
+    pub rows: Vec<String>,
+    pub columns: Vec<String>,
+    /// `cells[行][列]`
+    pub cells: Vec<Vec<usize>>,
+    pub row_totals: Vec<usize>,
+    pub column_totals: Vec<usize>,
+    pub total: usize,
+}
+
+impl CountMatrix {
+    /// 由「行 → 列 → 次数」的嵌套计数表构建
+    pub fn from_nested(table: &HashMap<String, HashMap<String, usize>>) -> Self {
+        let mut column_sums: HashMap<&String, usize> = HashMap::new();
+        for counts in table.values() {
+            for (column, count) in counts {
+                *column_sums.entry(column).or_insert(0) += count;
+            }
+        }
+        let row_sums: HashMap<&String, usize> = table
+            .iter()
+            .map(|(row, counts)| (row, counts.values().sum()))
+            .collect();
+
+        let rows = sorted_keys(&row_sums);
+        let columns = sorted_keys(&column_sums);
+        let cells: Vec<Vec<usize>> = rows
+            .iter()
+            .map(|row| {
+                columns
+                    .iter()
+                    .map(|column| table[row].get(column).copied().unwrap_or(0))
+                    .collect()
+            })
+            .collect();
+
+        CountMatrix {
+            row_totals: rows.iter().map(|row| row_sums[row]).collect(),
+            column_totals: columns.iter().map(|column| column_sums[column]).collect(),
+            total: row_sums.values().sum(),
+            rows,
+            columns,
+            cells,
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.rows.is_empty()
+    }
+
+    /// 导出为 CSV 文本：首行为列名与合计，末行为各列合计
+    pub fn to_csv(
+        &self,
+        corner: &str,
+        total_label: &str,
+        row_label: impl Fn(&str) -> String,
+        column_label: impl Fn(&str) -> String,
+    ) -> String {
+        let mut header = vec![csv_field(corner)];
+        header.extend(self.columns.iter().map(|c| csv_field(&column_label(c))));
+        header.push(csv_field(total_label));
+        let mut csv = format!("{}\n", header.join(","));
+
+        for ((row, cells), total) in self.rows.iter().zip(&self.cells).zip(&self.row_totals) {
+            let mut line = vec![csv_field(&row_label(row))];
+            line.extend(cells.iter().map(usize::to_string));
+            line.push(total.to_string());
+            csv.push_str(&format!("{}\n", line.join(",")));
+        }
+
+        let mut footer = vec![csv_field(total_label)];
+        footer.extend(self.column_totals.iter().map(usize::to_string));
+        footer.push(self.total.to_string());
+        csv.push_str(&format!("{}\n", footer.join(",")));
+        csv
+    }
+}
+
+fn sorted_keys(sums: &HashMap<&String, usize>) -> Vec<String> {
+    let mut keys: Vec<_> = sums.iter().collect();
+    keys.sort_by(|a, b| b.1.cmp(a.1).then(a.0.cmp(b.0)));
+    keys.into_iter().map(|(key, _)| (*key).clone()).collect()
+}
+
+/// 含逗号、引号或换行的字段加引号转义
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        format!("\"{}\"", value.replace('"', "\"\""))
+    } else {
+        value.to_string()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn nested(rows: &[(&str, &[(&str, usize)])]) -> HashMap<String, HashMap<String, usize>> {
+        rows.iter()
+            .map(|(row, counts)| {
+                let counts = counts.iter().map(|(c, n)| (c.to_string(), *n)).collect();
+                (row.to_string(), counts)
+            })
+            .collect()
+    }
+
+    #[test]
+    fn orders_rows_and_columns_by_total_with_totals() {
+        let matrix = CountMatrix::from_nested(&nested(&[
+            ("SGuard64.exe", &[("system", 5), ("game", 1)]),
+            ("SGuardSvc64.exe", &[("game", 7)]),
+        ]));
+        assert_eq!(matrix.rows, ["SGuardSvc64.exe", "SGuard64.exe"]);
+        assert_eq!(matrix.columns, ["game", "system"]);
+        assert_eq!(matrix.cells, [[7, 0], [1, 5]]);
+        assert_eq!(matrix.row_totals, [7, 6]);
+        assert_eq!(matrix.column_totals, [8, 5]);
+        assert_eq!(matrix.total, 13);
+    }
+
+    #[test]
+    fn csv_ends_with_a_total_row() {
+        let matrix = CountMatrix::from_nested(&nested(&[("a,b.exe", &[("dll", 2)])]));
+        let csv = matrix.to_csv("进程", "合计", str::to_string, |c| format!(".{}", c));
+        assert_eq!(csv, "进程,.dll,合计\n\"a,b.exe\",2,2\n合计,2,2\n");
+    }
+}
@@ -0,0 +1,975 @@
+use std::cmp::Reverse;
+use std::collections::HashMap;
+
+use chrono::TimeDelta;
+
+use crate::diff::{CountDiff, StatsDiff};
+use crate::history::HistoryTrend;
+use crate::i18n::{fill, Locale, Messages};
+use crate::matrix::CountMatrix;
+use crate::operation::OperationKind;
+use crate::outcome::OperationOutcome;
+use crate::options::ReportOptions;
+use crate::session::ScanSession;
+use crate::stats::AceScanStats;
+use crate::tree::DirTree;
+
+/// 与输出格式无关的报告数据模型；终端、Markdown 等渲染器只负责排版，
+/// 统计口径（排序、Top-N、占比、风险等级）全部在这里确定
+#[derive(Debug, Clone, Default)]
+pub struct ReportModel {
+    /// 渲染器自身文字（表头、图例等）使用的语言
+    pub locale: Locale,
+    /// 报告标题，为空时不输出标题区
+    pub title: Option<String>,
+    pub subtitle: Option<String>,
+    pub sections: Vec<Section>,
+}
+
+/// 报告中的一节，如「核心指标」
+#[derive(Debug, Clone)]
+pub struct Section {
+    /// 带图标的节标题，如 `📊 核心指标`
+    pub title: String,
+    pub blocks: Vec<Block>,
+}
+
+/// 节内的内容块
+#[derive(Debug, Clone)]
+pub enum Block {
+    /// 指标列表
+    Metrics(Vec<Metric>),
+    Table(Table),
+    /// 横向柱状图：`header` 为标签列名，`bars` 为标签与数值
+    Bars {
+        header: String,
+        bars: Vec<(String, usize)>,
+    },
+    /// 日期 × 小时热力矩阵：行标签与 24 个小时的计数
+    Heatmap(Vec<(String, [usize; 24])>),
+    /// 有序列表，条目内的 `\n` 表示续行
+    List(Vec<String>),
+    /// 完整目录树，仅 HTML 报告绘制为可折叠列表；终端与 Markdown 以同节的热点目录表代替
+    Tree(DirTree),
+}
+
+/// 单项指标
+#[derive(Debug, Clone)]
+pub struct Metric {
+    pub label: String,
+    pub value: String,
+    /// 数值后的补充说明，如拦截率
+    pub note: Option<String>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Table {
+    pub columns: Vec<Column>,
+    pub rows: Vec<Vec<String>>,
+    /// 合计行，排在数据行之后且不参与排序（HTML 中放在 `<tfoot>` 里）
+    pub footer: Option<Vec<String>>,
+}
+
+#[derive(Debug, Clone)]
+pub struct Column {
+    pub header: String,
+    pub kind: ColumnKind,
+}
+
+/// 列类型，决定对齐方式以及宽度不足时的处理
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ColumnKind {
+    /// 普通文本，左对齐
+    Text,
+    /// 数值，右对齐
+    Number,
+    /// 文件路径，占用剩余宽度，过长时省略中间部分
+    Path,
+}
+
+impl Metric {
+    fn new(label: &str, value: impl ToString) -> Self {
+        Metric {
+            label: label.to_string(),
+            value: value.to_string(),
+            note: None,
+        }
+    }
+
+    fn with_note(mut self, note: String) -> Self {
+        self.note = Some(note);
+        self
+    }
+}
+
+impl Table {
+    fn new(columns: &[(&str, ColumnKind)]) -> Self {
+        Table {
+            columns: columns
+                .iter()
+                .map(|(header, kind)| Column {
+                    header: header.to_string(),
+                    kind: *kind,
+                })
+                .collect(),
+            rows: Vec::new(),
+            footer: None,
+        }
+    }
+
+    fn push(&mut self, row: Vec<String>) {
+        self.rows.push(row);
+    }
+
+    /// 数据行与合计行（如有）
+    pub fn all_rows(&self) -> impl Iterator<Item = &Vec<String>> {
+        self.rows.iter().chain(&self.footer)
+    }
+}
+
+impl Section {
+    fn new(title: impl Into<String>, blocks: Vec<Block>) -> Self {
+        Section {
+            title: title.into(),
+            blocks,
+        }
+    }
+}
+
+impl ReportModel {
+    /// 完整分析报告：核心指标、进程、Top-N 目标、分类、扩展名、时间分布、会话与加固建议
+    pub fn detailed(stats: &AceScanStats, sessions: &[ScanSession], options: &ReportOptions) -> Self {
+        let m = options.locale.messages();
+        let mut sections = vec![
+            core_metrics_section(stats, m),
+            processes_section(stats, options),
+            top_files_section(stats, options),
+            hot_dirs_section(stats, options),
+            categories_section(stats, options),
+            extensions_section(stats, options),
+        ];
+        sections.extend(operations_section(stats, options));
+        sections.extend(process_matrix_section(stats, options));
+        sections.extend(outcomes_section(stats, options));
+        sections.extend(allowed_targets_section(stats, options));
+        sections.extend(time_distribution_section(stats, m));
+        sections.extend(timeline_sections(stats, options.locale));
+        if !sessions.is_empty() {
+            sections.push(sessions_section(sessions, options.locale));
+        }
+        sections.push(recommendations_section(m));
+
+        ReportModel {
+            locale: options.locale,
+            title: Some(m.report_title.to_string()),
+            subtitle: Some(fill(m.report_subtitle, &[&stats.total_attempts])),
+            sections,
+        }
+    }
+
+    /// 历史库报告：完整分析报告在核心指标之后加入按周、按机器的历史趋势
+    pub fn history(
+        stats: &AceScanStats,
+        sessions: &[ScanSession],
+        trend: &HistoryTrend,
+        options: &ReportOptions,
+    ) -> Self {
+        let mut model = Self::detailed(stats, sessions, options);
+        if !trend.is_empty() {
+            model.sections.insert(1, history_trend_section(trend, options.locale));
+        }
+        model
+    }
+
+    /// 仅包含游戏会话分析
+    pub fn sessions(sessions: &[ScanSession], options: &ReportOptions) -> Self {
+        ReportModel {
+            locale: options.locale,
+            sections: vec![sessions_section(sessions, options.locale)],
+            ..ReportModel::default()
+        }
+    }
+
+    /// 两次统计的对比：总体变化、新增/消失的目标、文件频次变化，以及分类、进程、规则的变化
+    pub fn diff(diff: &StatsDiff, before: &str, after: &str, options: &ReportOptions) -> Self {
+        let m = options.locale.messages();
+        let mut sections = vec![diff_summary_section(diff, m)];
+
+        let added: Vec<_> = diff.files.added().collect();
+        if !added.is_empty() {
+            let mut table = Table::new(&[(m.col_path, ColumnKind::Path), (m.col_after, ColumnKind::Number)]);
+            let mut sorted = added.clone();
+            sorted.sort_by(|a, b| b.after.cmp(&a.after).then(a.key.cmp(&b.key)));
+            for change in sorted.into_iter().take(options.top_files) {
+                table.push(vec![change.key.clone(), change.after.to_string()]);
+            }
+            sections.push(Section::new(
+                fill(m.section_new_targets, &[&added.len()]),
+                vec![Block::Table(table)],
+            ));
+        }
+
+        let removed: Vec<_> = diff.files.removed().collect();
+        if !removed.is_empty() {
+            let mut table = Table::new(&[(m.col_path, ColumnKind::Path), (m.col_before, ColumnKind::Number)]);
+            let mut sorted = removed.clone();
+            sorted.sort_by(|a, b| b.before.cmp(&a.before).then(a.key.cmp(&b.key)));
+            for change in sorted.into_iter().take(options.top_files) {
+                table.push(vec![change.key.clone(), change.before.to_string()]);
+            }
+            sections.push(Section::new(
+                fill(m.section_gone_targets, &[&removed.len()]),
+                vec![Block::Table(table)],
+            ));
+        }
+
+        let changed: Vec<_> = diff.files.changed().collect();
+        if !changed.is_empty() {
+            let mut table = Table::new(&[
+                (m.col_path, ColumnKind::Path),
+                (m.col_before, ColumnKind::Number),
+                (m.col_after, ColumnKind::Number),
+                (m.col_change, ColumnKind::Number),
+            ]);
+            for change in changed.iter().take(options.top_files) {
+                table.push(vec![
+                    change.key.clone(),
+                    change.before.to_string(),
+                    change.after.to_string(),
+                    format!("{:+}", change.delta()),
+                ]);
+            }
+            sections.push(Section::new(
+                fill(m.section_file_changes, &[&changed.len()]),
+                vec![Block::Table(table)],
+            ));
+        }
+
+        for (title, header, counts, is_category) in [
+            (m.section_category_changes, m.col_category, &diff.categories, true),
+            (m.section_process_changes, m.col_process, &diff.processes, false),
+            (m.section_rule_changes, m.col_rule, &diff.rules, false),
+        ] {
+            if counts.is_empty() {
+                continue;
+            }
+            let label = |key: &str| match is_category {
+                true => options.category_label(key).to_string(),
+                false => key.to_string(),
+            };
+            sections.push(Section::new(
+                title,
+                vec![Block::Table(count_diff_table(counts, header, m, label))],
+            ));
+        }
+
+        ReportModel {
+            locale: options.locale,
+            title: Some(m.diff_title.to_string()),
+            subtitle: Some(format!("{} → {}", before, after)),
+            sections,
+        }
+    }
+
+    /// 仅包含触犯规则统计
+    pub fn rules(stats: &AceScanStats, options: &ReportOptions) -> Self {
+        ReportModel {
+            locale: options.locale,
+            sections: vec![rules_section(stats, options.locale.messages())],
+            ..ReportModel::default()
+        }
+    }
+}
+
+fn percent(count: usize, total: usize) -> String {
+    let p = if total > 0 {
+        count as f64 / total as f64 * 100.0
+    } else {
+        0.0
+    };
+    format!("{:.1}%", p)
+}
+
+/// 按次数降序（次数相同按名称）排列
+fn sorted_counts<'a, I>(counts: I) -> Vec<(&'a String, usize)>
+where
+    I: IntoIterator<Item = (&'a String, &'a usize)>,
+{
+    let mut sorted: Vec<_> = counts.into_iter().map(|(k, v)| (k, *v)).collect();
+    sorted.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+    sorted
+}
+
+/// 启动参数的显示形式，统计中以空字符串表示无参数
+pub(crate) fn arguments_label(arguments: &str, m: &Messages) -> String {
+    if arguments.is_empty() {
+        m.no_arguments.to_string()
+    } else {
+        arguments.to_string()
+    }
+}
+
+/// 扩展名的显示形式，统计中以空字符串表示无扩展名
+pub(crate) fn extension_label(ext: &str, m: &Messages) -> String {
+    if ext.is_empty() {
+        m.no_extension.to_string()
+    } else {
+        format!(".{}", ext)
+    }
+}
+
+fn core_metrics_section(stats: &AceScanStats, m: &Messages) -> Section {
+    let block_rate = format!("{:.1}%", stats.block_rate());
+    let mut metrics = vec![
+        Metric::new(m.metric_total, stats.total_attempts),
+        Metric::new(m.metric_blocked, stats.blocked_attempts)
+            .with_note(fill(m.note_block_rate, &[&block_rate])),
+        Metric::new(m.metric_unique_files, stats.unique_files.len()),
+        Metric::new(m.metric_processes, stats.processes.len()),
+    ];
+    if stats.duplicate_entries > 0 {
+        metrics.push(Metric::new(m.metric_duplicates, stats.duplicate_entries));
+    }
+    Section::new(m.section_core, vec![Block::Metrics(metrics)])
+}
+
+fn processes_section(stats: &AceScanStats, options: &ReportOptions) -> Section {
+    let m = options.locale.messages();
+    let mut table = Table::new(&[
+        (m.col_rank, ColumnKind::Number),
+        (m.col_process, ColumnKind::Text),
+        (m.col_scans, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+        (m.col_risk, ColumnKind::Text),
+    ]);
+    for (i, (proc, count)) in sorted_counts(&stats.processes)
+        .into_iter()
+        .take(options.top_processes)
+        .enumerate()
+    {
+        let risk = options.process_risk.level(count);
+        table.push(vec![
+            format!("{}.", i + 1),
+            proc.clone(),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+            format!("{} {}", risk.icon(), risk.label(options.locale)),
+        ]);
+    }
+
+    let mut instances = Table::new(&[
+        (m.col_process_path, ColumnKind::Path),
+        (m.col_arguments, ColumnKind::Path),
+        (m.col_scans, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+    ]);
+    for (path, arguments, count) in stats
+        .sorted_process_instances()
+        .into_iter()
+        .take(options.top_processes)
+    {
+        instances.push(vec![
+            path.to_string(),
+            arguments_label(arguments, m),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+        ]);
+    }
+
+    let mut blocks = vec![Block::Table(table)];
+    if !instances.rows.is_empty() {
+        blocks.push(Block::Table(instances));
+    }
+    Section::new(m.section_processes, blocks)
+}
+
+fn top_files_section(stats: &AceScanStats, options: &ReportOptions) -> Section {
+    let m = options.locale.messages();
+    let mut table = Table::new(&[
+        (m.col_rank, ColumnKind::Number),
+        (m.col_path, ColumnKind::Path),
+        (m.col_hits, ColumnKind::Number),
+        (m.col_risk, ColumnKind::Text),
+    ]);
+    for (i, (file, count)) in sorted_counts(&stats.unique_files)
+        .into_iter()
+        .take(options.top_files)
+        .enumerate()
+    {
+        table.push(vec![
+            format!("{}.", i + 1),
+            file.clone(),
+            count.to_string(),
+            options.file_risk.level(count).icon().to_string(),
+        ]);
+    }
+    Section::new(
+        fill(m.section_top_files, &[&options.top_files]),
+        vec![Block::Table(table)],
+    )
+}
+
+/// 热点目录：按目录树先序列出每层扫描最多的子目录，路径本身体现层级
+fn hot_dirs_section(stats: &AceScanStats, options: &ReportOptions) -> Section {
+    let m = options.locale.messages();
+    let mut table = Table::new(&[
+        (m.col_dir, ColumnKind::Path),
+        (m.col_scans, ColumnKind::Number),
+        (m.col_self, ColumnKind::Number),
+        (m.col_files, ColumnKind::Number),
+        (m.col_blocked, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+    ]);
+    let tree = DirTree::from_stats(stats);
+    for (path, node) in tree.hot_dirs(options.tree_depth, options.top_dirs) {
+        table.push(vec![
+            path,
+            node.subtree_count.to_string(),
+            node.self_count.to_string(),
+            node.unique_files.to_string(),
+            node.blocked_count.to_string(),
+            percent(node.subtree_count, stats.total_attempts),
+        ]);
+    }
+    Section::new(
+        fill(m.section_hot_dirs, &[&options.tree_depth]),
+        vec![Block::Table(table), Block::Tree(tree)],
+    )
+}
+
+fn categories_section(stats: &AceScanStats, options: &ReportOptions) -> Section {
+    let m = options.locale.messages();
+    let mut table = Table::new(&[
+        (m.col_category, ColumnKind::Text),
+        (m.col_scans, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+        (m.col_risk, ColumnKind::Text),
+    ]);
+    for (cat, count) in sorted_counts(&stats.target_categories) {
+        table.push(vec![
+            options.category_label(cat).to_string(),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+            options.category_risk.level(count).icon().to_string(),
+        ]);
+    }
+    Section::new(m.section_categories, vec![Block::Table(table)])
+}
+
+fn extensions_section(stats: &AceScanStats, options: &ReportOptions) -> Section {
+    let m = options.locale.messages();
+    let mut table = Table::new(&[
+        (m.col_extension, ColumnKind::Text),
+        (m.col_count, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+    ]);
+    for (ext, count) in sorted_counts(&stats.file_extensions)
+        .into_iter()
+        .take(options.top_extensions)
+    {
+        table.push(vec![
+            extension_label(ext, m),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+        ]);
+    }
+    Section::new(m.section_extensions, vec![Block::Table(table)])
+}
+
+/// 操作类型分布，以及按分类、按进程的操作类型交叉表；日志中没有操作类型时省略
+fn operations_section(stats: &AceScanStats, options: &ReportOptions) -> Option<Section> {
+    if stats.operation_types.is_empty() {
+        return None;
+    }
+    let m = options.locale.messages();
+    let operations: Vec<_> = OperationKind::ALL
+        .into_iter()
+        .filter(|op| stats.operation_types.contains_key(op.id()))
+        .collect();
+
+    let mut summary = Table::new(&[
+        (m.col_operation, ColumnKind::Text),
+        (m.col_count, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+    ]);
+    for (op, count) in sorted_counts(&stats.operation_types) {
+        let label = op.parse::<OperationKind>().map_or(op.as_str(), |k| k.label(options.locale));
+        summary.push(vec![
+            label.to_string(),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+        ]);
+    }
+
+    let columns: Vec<_> = operations
+        .iter()
+        .map(|op| (op.id(), op.label(options.locale)))
+        .collect();
+    let by_category = id_crosstab(
+        m.col_category,
+        rows_by_total(&stats.category_operations, usize::MAX)
+            .into_iter()
+            .map(|(id, counts)| (options.category_label(id).to_string(), counts)),
+        &columns,
+        m,
+    );
+    let by_process = id_crosstab(
+        m.col_process,
+        rows_by_total(&stats.process_operations, options.top_processes)
+            .into_iter()
+            .map(|(name, counts)| (name.clone(), counts)),
+        &columns,
+        m,
+    );
+    Some(Section::new(
+        m.section_operations,
+        vec![
+            Block::Table(summary),
+            Block::Table(by_category),
+            Block::Table(by_process),
+        ],
+    ))
+}
+
+/// 操作结果分布，以及按分类、进程、规则、时段细分的结果交叉表
+fn outcomes_section(stats: &AceScanStats, options: &ReportOptions) -> Option<Section> {
+    if stats.outcomes.is_empty() {
+        return None;
+    }
+    let m = options.locale.messages();
+    let outcomes: Vec<_> = OperationOutcome::ALL
+        .into_iter()
+        .filter(|outcome| stats.outcomes.contains_key(outcome.id()))
+        .collect();
+
+    let mut summary = Table::new(&[
+        (m.col_outcome, ColumnKind::Text),
+        (m.col_count, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+    ]);
+    for outcome in &outcomes {
+        let count = stats.outcomes[outcome.id()];
+        summary.push(vec![
+            outcome.label(options.locale).to_string(),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+        ]);
+    }
+
+    let columns: Vec<_> = outcomes
+        .iter()
+        .map(|outcome| (outcome.id(), outcome.label(options.locale)))
+        .collect();
+    let by_category = id_crosstab(
+        m.col_category,
+        rows_by_total(&stats.category_outcomes, usize::MAX)
+            .into_iter()
+            .map(|(id, counts)| (options.category_label(id).to_string(), counts)),
+        &columns,
+        m,
+    );
+    let by_process = id_crosstab(
+        m.col_process,
+        rows_by_total(&stats.process_outcomes, options.top_processes)
+            .into_iter()
+            .map(|(name, counts)| (name.clone(), counts)),
+        &columns,
+        m,
+    );
+    let by_rule = id_crosstab(
+        m.col_rule,
+        rows_by_total(&stats.rule_outcomes, usize::MAX)
+            .into_iter()
+            .map(|(name, counts)| (name.clone(), counts)),
+        &columns,
+        m,
+    );
+    let by_hour = id_crosstab(
+        m.col_hour,
+        stats.hour_outcomes.iter().map(|(hour, counts)| (hour.clone(), counts)),
+        &columns,
+        m,
+    );
+
+    let mut blocks = vec![Block::Table(summary), Block::Table(by_category), Block::Table(by_process)];
+    for table in [by_rule, by_hour] {
+        if !table.rows.is_empty() {
+            blocks.push(Block::Table(table));
+        }
+    }
+    Some(Section::new(m.section_outcomes, blocks))
+}
+
+/// 被放行（含询问后允许）次数最多的前 `top_files` 个目标，即当前规则没有拦住的扫描
+fn allowed_targets_section(stats: &AceScanStats, options: &ReportOptions) -> Option<Section> {
+    let targets = stats.allowed_targets();
+    if targets.is_empty() {
+        return None;
+    }
+    let m = options.locale.messages();
+    let mut table = Table::new(&[
+        (m.col_rank, ColumnKind::Number),
+        (m.col_path, ColumnKind::Path),
+        (m.col_allowed, ColumnKind::Number),
+        (m.col_scans, ColumnKind::Number),
+    ]);
+    for (i, (file, allowed, total)) in targets.iter().take(options.top_files).enumerate() {
+        table.push(vec![
+            format!("{}.", i + 1),
+            file.to_string(),
+            allowed.to_string(),
+            total.to_string(),
+        ]);
+    }
+    Some(Section::new(
+        fill(m.section_allowed_targets, &[&targets.len()]),
+        vec![Block::Table(table)],
+    ))
+}
+
+/// 进程 × 分类与进程 × 扩展名交叉表，显示前 `top_processes` 个进程与前 `top_extensions` 种扩展名
+fn process_matrix_section(stats: &AceScanStats, options: &ReportOptions) -> Option<Section> {
+    let by_category = CountMatrix::from_nested(&stats.process_categories);
+    if by_category.is_empty() {
+        return None;
+    }
+    let by_extension = CountMatrix::from_nested(&stats.process_extensions);
+    let m = options.locale.messages();
+    Some(Section::new(
+        m.section_process_matrix,
+        vec![
+            Block::Table(matrix_table(&by_category, options.top_processes, usize::MAX, m, |id| {
+                options.category_label(id).to_string()
+            })),
+            Block::Table(matrix_table(
+                &by_extension,
+                options.top_processes,
+                options.top_extensions,
+                m,
+                |ext| extension_label(ext, m),
+            )),
+        ],
+    ))
+}
+
+/// 进程为行的交叉表：末列为行合计、合计行为列合计，超出 `max_columns` 的列并入「其他」
+fn matrix_table(
+    matrix: &CountMatrix,
+    max_rows: usize,
+    max_columns: usize,
+    m: &Messages,
+    column_label: impl Fn(&str) -> String,
+) -> Table {
+    let shown = matrix.columns.len().min(max_columns);
+    let has_other = shown < matrix.columns.len();
+    let labels: Vec<String> = matrix.columns[..shown].iter().map(|c| column_label(c)).collect();
+
+    let mut columns = vec![(m.col_process, ColumnKind::Text)];
+    columns.extend(labels.iter().map(|label| (label.as_str(), ColumnKind::Number)));
+    if has_other {
+        columns.push((m.other_columns, ColumnKind::Number));
+    }
+    columns.push((m.total, ColumnKind::Number));
+    let mut table = Table::new(&columns);
+
+    let row_cells = |name: &str, cells: &[usize], total: usize| {
+        let mut row = vec![name.to_string()];
+        row.extend(cells[..shown].iter().map(usize::to_string));
+        if has_other {
+            row.push(cells[shown..].iter().sum::<usize>().to_string());
+        }
+        row.push(total.to_string());
+        row
+    };
+    for ((name, cells), total) in matrix
+        .rows
+        .iter()
+        .zip(&matrix.cells)
+        .zip(&matrix.row_totals)
+        .take(max_rows)
+    {
+        table.push(row_cells(name, cells, *total));
+    }
+    table.footer = Some(row_cells(m.total, &matrix.column_totals, matrix.total));
+    table
+}
+
+/// 列为操作类型或操作结果等固定取值（`(ID, 显示名)`）的交叉表，行按传入顺序排列
+fn id_crosstab<'a>(
+    header: &str,
+    rows: impl IntoIterator<Item = (String, &'a HashMap<String, usize>)>,
+    columns: &[(&str, &str)],
+    m: &Messages,
+) -> Table {
+    let mut headers = vec![(header, ColumnKind::Text)];
+    headers.extend(columns.iter().map(|(_, label)| (*label, ColumnKind::Number)));
+    headers.push((m.total, ColumnKind::Number));
+    let mut table = Table::new(&headers);
+
+    for (label, counts) in rows {
+        let mut cells = vec![label];
+        cells.extend(
+            columns
+                .iter()
+                .map(|(id, _)| counts.get(*id).copied().unwrap_or(0).to_string()),
+        );
+        cells.push(counts.values().sum::<usize>().to_string());
+        table.push(cells);
+    }
+    table
+}
+
+/// 嵌套计数表的行按合计降序（相同时按名称）排列，取前 `limit` 行
+pub(crate) fn rows_by_total(
+    rows: &HashMap<String, HashMap<String, usize>>,
+    limit: usize,
+) -> Vec<(&String, &HashMap<String, usize>)> {
+    let mut sorted: Vec<_> = rows.iter().collect();
+    sorted.sort_by_cached_key(|(name, counts)| (Reverse(counts.values().sum::<usize>()), *name));
+    sorted.truncate(limit);
+    sorted
+}
+
+fn time_distribution_section(stats: &AceScanStats, m: &Messages) -> Option<Section> {
+    let (peak_time, peak_count) = stats
+        .time_distribution
+        .iter()
+        .max_by(|a, b| a.1.cmp(b.1).then(b.0.cmp(a.0)))?;
+    Some(Section::new(
+        m.section_time,
+        vec![
+            Block::Metrics(vec![Metric::new(
+                m.metric_peak,
+                fill(m.peak_value, &[peak_time, peak_count]),
+            )]),
+            Block::Bars {
+                header: m.col_hour.to_string(),
+                bars: stats
+                    .time_distribution
+                    .iter()
+                    .map(|(k, v)| (k.clone(), *v))
+                    .collect(),
+            },
+        ],
+    ))
+}
+
+fn timeline_sections(stats: &AceScanStats, locale: Locale) -> Vec<Section> {
+    let (Some(first), Some(last)) = (stats.first_seen, stats.last_seen) else {
+        return Vec::new();
+    };
+    let m = locale.messages();
+    vec![
+        Section::new(
+            m.section_timeline,
+            vec![
+                Block::Metrics(vec![
+                    Metric::new(m.metric_first_seen, first.format("%Y-%m-%d %H:%M:%S")),
+                    Metric::new(m.metric_last_seen, last.format("%Y-%m-%d %H:%M:%S")),
+                    Metric::new(m.metric_span, format_duration(last - first, locale)),
+                ]),
+                Block::Bars {
+                    header: m.col_date.to_string(),
+                    bars: stats
+                        .daily_totals
+                        .iter()
+                        .map(|(day, count)| (day.to_string(), *count))
+                        .collect(),
+                },
+            ],
+        ),
+        Section::new(
+            m.section_heatmap,
+            vec![Block::Heatmap(
+                stats
+                    .day_hour_matrix
+                    .iter()
+                    .map(|(day, hours)| (day.to_string(), *hours))
+                    .collect(),
+            )],
+        ),
+    ]
+}
+
+fn sessions_section(sessions: &[ScanSession], locale: Locale) -> Section {
+    let m = locale.messages();
+    let mut table = Table::new(&[
+        ("#", ColumnKind::Number),
+        (m.col_start, ColumnKind::Text),
+        (m.col_end, ColumnKind::Text),
+        (m.col_duration, ColumnKind::Text),
+        (m.col_scans, ColumnKind::Number),
+        (m.col_files, ColumnKind::Number),
+        (m.metric_block_rate, ColumnKind::Number),
+    ]);
+    let mut targets = Table::new(&[
+        ("#", ColumnKind::Number),
+        (m.col_top_target, ColumnKind::Path),
+        (m.col_count, ColumnKind::Number),
+    ]);
+    for (i, session) in sessions.iter().enumerate() {
+        // 跨天的会话结束时间带上日期
+        let end_format = if session.end.date() == session.start.date() {
+            "%H:%M:%S"
+        } else {
+            "%Y-%m-%d %H:%M:%S"
+        };
+        table.push(vec![
+            (i + 1).to_string(),
+            session.start.format("%Y-%m-%d %H:%M:%S").to_string(),
+            session.end.format(end_format).to_string(),
+            format_duration(session.duration(), locale),
+            session.event_count.to_string(),
+            session.unique_files().to_string(),
+            format!("{:.1}%", session.block_rate()),
+        ]);
+        for (file, count) in session.top_targets(3) {
+            targets.push(vec![(i + 1).to_string(), file.to_string(), count.to_string()]);
+        }
+    }
+    Section::new(
+        fill(m.section_sessions, &[&sessions.len()]),
+        vec![Block::Table(table), Block::Table(targets)],
+    )
+}
+
+/// 每周每台机器一行的扫描、拦截与放行次数
+fn history_trend_section(trend: &HistoryTrend, locale: Locale) -> Section {
+    let m = locale.messages();
+    let mut table = Table::new(&[
+        (m.col_week, ColumnKind::Text),
+        (m.col_machine, ColumnKind::Text),
+        (m.col_scans, ColumnKind::Number),
+        (m.col_blocked, ColumnKind::Number),
+        (m.col_allowed, ColumnKind::Number),
+        (m.metric_block_rate, ColumnKind::Number),
+    ]);
+    for (week, machines) in &trend.weeks {
+        for (machine, counts) in machines {
+            table.push(vec![
+                if week.is_empty() { "-".to_string() } else { week.clone() },
+                machine.clone(),
+                counts.scans.to_string(),
+                counts.blocked.to_string(),
+                counts.allowed.to_string(),
+                percent(counts.blocked, counts.scans),
+            ]);
+        }
+    }
+    Section::new(
+        fill(m.section_history_trend, &[&trend.machines()]),
+        vec![Block::Table(table)],
+    )
+}
+
+fn rules_section(stats: &AceScanStats, m: &Messages) -> Section {
+    let mut table = Table::new(&[
+        (m.col_rule, ColumnKind::Text),
+        (m.col_count, ColumnKind::Number),
+        (m.col_share, ColumnKind::Number),
+    ]);
+    for (rule, count) in sorted_counts(&stats.rules_triggered) {
+        table.push(vec![
+            rule.clone(),
+            count.to_string(),
+            percent(count, stats.total_attempts),
+        ]);
+    }
+    Section::new(
+        fill(m.section_rules, &[&stats.rules_triggered.len()]),
+        vec![Block::Table(table)],
+    )
+}
+
+fn diff_summary_section(diff: &StatsDiff, m: &Messages) -> Section {
+    let mut table = Table::new(&[
+        (m.metric, ColumnKind::Text),
+        (m.col_before, ColumnKind::Number),
+        (m.col_after, ColumnKind::Number),
+        (m.col_change, ColumnKind::Number),
+    ]);
+    for (label, before, after) in [
+        (m.metric_total, diff.total_before, diff.total_after),
+        (m.metric_blocked, diff.blocked_before, diff.blocked_after),
+        (m.metric_unique_files, diff.unique_files_before, diff.unique_files_after),
+    ] {
+        table.push(vec![
+            label.to_string(),
+            before.to_string(),
+            after.to_string(),
+            format!("{:+}", after as i64 - before as i64),
+        ]);
+    }
+    table.push(vec![
+        m.metric_block_rate.to_string(),
+        format!("{:.1}%", diff.block_rate_before),
+        format!("{:.1}%", diff.block_rate_after),
+        fill(m.percentage_points, &[&format_args!("{:+.1}", diff.block_rate_change())]),
+    ]);
+
+    let mut blocks = vec![Block::Table(table)];
+    let unchanged = [&diff.files, &diff.categories, &diff.processes, &diff.rules]
+        .iter()
+        .all(|d| d.is_empty());
+    if unchanged && diff.total_before == diff.total_after {
+        blocks.push(Block::List(vec![m.no_changes.to_string()]));
+    }
+    Section::new(m.section_diff_summary, blocks)
+}
+
+/// 名称、之前、之后、变化与状态（新增/消失）五列的对比表
+fn count_diff_table(
+    counts: &CountDiff,
+    header: &str,
+    m: &Messages,
+    label: impl Fn(&str) -> String,
+) -> Table {
+    let mut table = Table::new(&[
+        (header, ColumnKind::Text),
+        (m.col_before, ColumnKind::Number),
+        (m.col_after, ColumnKind::Number),
+        (m.col_change, ColumnKind::Number),
+        (m.col_status, ColumnKind::Text),
+    ]);
+    for change in &counts.entries {
+        let status = if change.is_added() {
+            m.status_added
+        } else if change.is_removed() {
+            m.status_removed
+        } else {
+            ""
+        };
+        table.push(vec![
+            label(&change.key),
+            change.before.to_string(),
+            change.after.to_string(),
+            format!("{:+}", change.delta()),
+            status.to_string(),
+        ]);
+    }
+    table
+}
+
+fn recommendations_section(m: &Messages) -> Section {
+    Section::new(
+        m.section_recommendations,
+        vec![Block::List(m.recommendations.iter().map(|r| r.to_string()).collect())],
+    )
+}
+
+/// 按 `locale` 将时长格式化为「X 天 X 小时 X 分」等形式
+pub(crate) fn format_duration(delta: TimeDelta, locale: Locale) -> String {
+    let m = locale.messages();
+    let days = delta.num_days();
+    let hours = delta.num_hours() % 24;
+    let minutes = delta.num_minutes() % 60;
+    if days > 0 {
+        fill(m.duration_days, &[&days, &hours, &minutes])
+    } else if hours > 0 {
+        fill(m.duration_hours, &[&hours, &minutes])
+    } else if minutes > 0 {
+        fill(m.duration_minutes, &[&minutes, &(delta.num_seconds() % 60)])
+    } else {
+        fill(m.duration_seconds, &[&delta.num_seconds()])
+    }
+}
@@ -0,0 +1,439 @@
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
+
+use crate::entry::AceLogEntry;
+use crate::winpath::WinPath;
+
+/// 用户名目录折叠后的占位名
+pub const USER_PLACEHOLDER: &str = "<user>";
+
+/// `C:\Users` 下不属于具体用户、不做折叠的目录
+const SHARED_PROFILES: &[&str] = &["public", "default", "default user", "all users"];
+
+/// 常见目录的标准大小写写法
+const CANONICAL_NAMES: &[&str] = &[
+    "Windows",
+    "System32",
+    "SysWOW64",
+    "drivers",
+    "WinSxS",
+    "Program Files",
+    "Program Files (x86)",
+    "Common Files",
+    "ProgramData",
+    "Users",
+    "AppData",
+    "Local",
+    "LocalLow",
+    "Roaming",
+    "Temp",
+    "Microsoft.NET",
+];
+
+/// 可还原的 8.3 短文件名：（上级目录，短名，长名），上级为空表示位于根目录下
+const SHORT_NAMES: &[(&str, &str, &str)] = &[
+    ("", "progra~1", "Program Files"),
+    ("", "progra~2", "Program Files (x86)"),
+    ("", "progra~3", "ProgramData"),
+    ("", "docume~1", "Documents and Settings"),
+    ("program files", "common~1", "Common Files"),
+    ("program files (x86)", "common~1", "Common Files"),
+];
+
+/// 设备卷号到盘符的映射，如 `\Device\HarddiskVolume3` → `C:`
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct VolumeMap(BTreeMap<u32, String>);
+
+impl VolumeMap {
+    /// 添加一条映射，`drive` 可写作 `C` 或 `C:`
+    pub fn insert(&mut self, volume: u32, drive: &str) {
+        let letter = drive.trim().trim_end_matches(':').to_uppercase();
+        self.0.insert(volume, format!("{}:", letter));
+    }
+
+    /// 卷号对应的盘符
+    pub fn drive(&self, volume: u32) -> Option<&str> {
+        self.0.get(&volume).map(String::as_str)
+    }
+
+    /// 是否没有任何映射
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromStr for VolumeMap {
+    type Err = String;
+
+    /// 解析 `3=C,4=D` 格式（卷号也可写作 `HarddiskVolume3`）
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut map = VolumeMap::default();
+        for item in s.split(',').map(str::trim).filter(|i| !i.is_empty()) {
+            let (volume, drive) = item.split_once('=').ok_or_else(|| {
+                format!(
+                    "卷映射格式应为 `卷号=盘符`，如 `3=C` / expected `N=DRIVE`, e.g. `3=C`: {}",
+                    item
+                )
+            })?;
+            let volume = volume.trim();
+            let number = strip_volume_prefix(volume)
+                .unwrap_or(volume)
+                .parse::<u32>()
+                .map_err(|e| format!("无效的卷号 / invalid volume `{}`: {}", volume, e))?;
+            let letter = drive.trim().trim_end_matches(':');
+            if letter.len() != 1 || !letter.chars().all(|c| c.is_ascii_alphabetic()) {
+                return Err(format!("无效的盘符 / invalid drive letter `{}`", drive.trim()));
+            }
+            map.insert(number, letter);
+        }
+        Ok(map)
+    }
+}
+
+impl fmt::Display for VolumeMap {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let items: Vec<String> = self.0.iter().map(|(v, d)| format!("{}={}", v, d)).collect();
+        write!(f, "{}", items.join(","))
+    }
+}
+
+/// Windows 路径规范化：把同一文件的不同写法统一为一种，供汇总统计使用
+///
+/// 依次处理：环境变量（`%SystemRoot%` 等）与 `\SystemRoot\` 前缀、`\\?\` 等前缀、
+/// 设备卷路径（按 [`VolumeMap`] 换成盘符）、可还原的 8.3 短文件名、
+/// `C:\Users\<用户名>` 折叠为 [`USER_PLACEHOLDER`]、常见目录的大小写；
+/// 其余仅大小写不同的路径统一为首次出现的写法。
+#[derive(Debug, Clone)]
+pub struct PathNormalizer {
+    volumes: VolumeMap,
+    system_drive: String,
+    collapse_users: bool,
+    /// 小写路径 → 首次出现的写法
+    spellings: HashMap<String, String>,
+    raw_targets: HashSet<String>,
+    canonical_targets: HashSet<String>,
+}
+
+impl Default for PathNormalizer {
+    fn default() -> Self {
+        PathNormalizer {
+            volumes: VolumeMap::default(),
+            system_drive: "C:".to_string(),
+            collapse_users: true,
+            spellings: HashMap::new(),
+            raw_targets: HashSet::new(),
+            canonical_targets: HashSet::new(),
+        }
+    }
+}
+
+impl PathNormalizer {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// 设置设备卷号到盘符的映射
+    pub fn with_volumes(mut self, volumes: VolumeMap) -> Self {
+        self.volumes = volumes;
+        self
+    }
+
+    /// 设置展开 `%SystemRoot%` 等环境变量时使用的系统盘（默认 `C:`）
+    pub fn with_system_drive(mut self, drive: &str) -> Self {
+        self.system_drive = format!("{}:", drive.trim().trim_end_matches(':').to_uppercase());
+        self
+    }
+
+    /// 是否把 `C:\Users\<用户名>` 折叠为 [`USER_PLACEHOLDER`]（默认是）；
+    /// 不折叠时 `%USERPROFILE%` 等用户目录变量不展开
+    pub fn collapse_user_names(mut self, collapse: bool) -> Self {
+        self.collapse_users = collapse;
+        self
+    }
+
+    /// 返回路径的规范写法
+    pub fn normalize(&mut self, raw: &str) -> String {
+        let expanded = self.expand_env(raw.trim());
+        let leading_separator = expanded.starts_with(['\\', '/']);
+        let mut path = WinPath::parse(&expanded);
+
+        self.map_root(&mut path);
+        expand_short_names(&mut path);
+        if self.collapse_users {
+            collapse_user(&mut path);
+        }
+        for component in &mut path.components {
+            if let Some(name) = CANONICAL_NAMES.iter().find(|n| n.eq_ignore_ascii_case(component)) {
+                *component = name.to_string();
+            }
+        }
+
+        let joined = path.components.join("\\");
+        let normalized = match &path.root {
+            Some(root) if joined.is_empty() => format!("{}\\", root),
+            Some(root) => format!("{}\\{}", root, joined),
+            None if leading_separator => format!("\\{}", joined),
+            None => joined,
+        };
+        self.spellings
+            .entry(normalized.to_lowercase())
+            .or_insert(normalized)
+            .clone()
+    }
+
+    /// 规范化条目中的操作文件与进程路径，原始写法保留在 `raw_*` 字段
+    pub fn normalize_entry(&mut self, entry: &mut AceLogEntry) {
+        if let Some(raw) = entry.target_file.take() {
+            let normalized = self.normalize(&raw);
+            self.canonical_targets.insert(normalized.clone());
+            self.raw_targets.insert(raw.clone());
+            entry.target_file = Some(normalized);
+            entry.raw_target_file = Some(raw);
+        }
+        if let Some(raw) = entry.process_path.take() {
+            entry.process_path = Some(self.normalize(&raw));
+            entry.raw_process_path = Some(raw);
+        }
+    }
+
+    /// 经 [`normalize_entry`](Self::normalize_entry) 处理的操作文件中，被合并掉的写法数
+    pub fn merged_targets(&self) -> usize {
+        self.raw_targets.len().saturating_sub(self.canonical_targets.len())
+    }
+
+    /// 展开 `%SystemRoot%` 等常见环境变量，无法识别的保持原样
+    ///
+    /// 用户目录相关的变量（`%USERPROFILE%`、`%APPDATA%` 等）只在折叠用户名时展开为
+    /// `Users\<user>`；保留用户名时日志中没有真实用户名可填，保持原样。
+    fn expand_env(&self, path: &str) -> String {
+        let drive = &self.system_drive;
+        let profile = self
+            .collapse_users
+            .then(|| format!("{}\\Users\\{}", drive, USER_PLACEHOLDER));
+        let mut out = String::new();
+        let mut rest = path;
+        while let Some(start) = rest.find('%') {
+            let Some(len) = rest[start + 1..].find('%') else {
+                break;
+            };
+            let name = &rest[start + 1..start + 1 + len];
+            let value = match name.to_ascii_lowercase().as_str() {
+                "systemroot" | "windir" => Some(format!("{}\\Windows", drive)),
+                "systemdrive" => Some(drive.clone()),
+                "programfiles" | "programw6432" => Some(format!("{}\\Program Files", drive)),
+                "programfiles(x86)" => Some(format!("{}\\Program Files (x86)", drive)),
+                "commonprogramfiles" => Some(format!("{}\\Program Files\\Common Files", drive)),
+                "programdata" | "allusersprofile" => Some(format!("{}\\ProgramData", drive)),
+                "public" => Some(format!("{}\\Users\\Public", drive)),
+                "userprofile" => profile.clone(),
+                "appdata" => profile.as_ref().map(|p| format!("{}\\AppData\\Roaming", p)),
+                "localappdata" => profile.as_ref().map(|p| format!("{}\\AppData\\Local", p)),
+                "temp" | "tmp" => {
+                    profile.as_ref().map(|p| format!("{}\\AppData\\Local\\Temp", p))
+                }
+                _ => None,
+            };
+            match value {
+                Some(value) => {
+                    out.push_str(&rest[..start]);
+                    out.push_str(&value);
+                }
+                None => out.push_str(&rest[..start + len + 2]),
+            }
+            rest = &rest[start + len + 2..];
+        }
+        out.push_str(rest);
+        out
+    }
+
+    /// 设备卷换成盘符；内核路径 `\SystemRoot\...` 换成系统盘下的 `Windows`
+    fn map_root(&self, path: &mut WinPath) {
+        if let Some(root) = &path.root {
+            let volume = root
+                .rsplit('\\')
+                .next()
+                .and_then(strip_volume_prefix)
+                .and_then(|v| v.parse::<u32>().ok());
+            if let Some(drive) = volume.and_then(|v| self.volumes.drive(v)) {
+                path.root = Some(drive.to_string());
+            }
+        } else if path
+            .components
+            .first()
+            .is_some_and(|c| c.eq_ignore_ascii_case("systemroot"))
+        {
+            path.root = Some(self.system_drive.clone());
+            path.components[0] = "Windows".to_string();
+        }
+    }
+}
+
+/// 去掉 `HarddiskVolume` 前缀（不区分大小写），返回卷号部分
+fn strip_volume_prefix(name: &str) -> Option<&str> {
+    const PREFIX: &str = "harddiskvolume";
+    name.get(..PREFIX.len())
+        .filter(|p| p.eq_ignore_ascii_case(PREFIX))
+        .map(|_| &name[PREFIX.len()..])
+}
+
+/// 还原可确定长名的 8.3 短文件名，无法确定的保持原样
+fn expand_short_names(path: &mut WinPath) {
+    for i in 0..path.components.len() {
+        if !path.components[i].contains('~') {
+            continue;
+        }
+        let parent = match i {
+            0 => String::new(),
+            _ => path.components[i - 1].to_lowercase(),
+        };
+        let short = path.components[i].to_lowercase();
+        if let Some((_, _, long)) = SHORT_NAMES
+            .iter()
+            .find(|(p, s, _)| *p == parent && *s == short)
+        {
+            path.components[i] = long.to_string();
+        }
+    }
+}
+
+/// `<盘符>\Users\<用户名>` 折叠为 `Users\<user>`，公共目录保持不变；
+/// UNC 共享与未映射的设备卷下的 `Users` 不一定是用户目录，不做折叠
+fn collapse_user(path: &mut WinPath) {
+    let on_drive = path.root.as_deref().is_some_and(|root| root.ends_with(':'));
+    if !on_drive || path.components.len() < 2 {
+        return;
+    }
+    if !path.components[0].eq_ignore_ascii_case("users") {
+        return;
+    }
+    let name = path.components[1].to_lowercase();
+    if !SHARED_PROFILES.contains(&name.as_str()) {
+        path.components[1] = USER_PLACEHOLDER.to_string();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn volumes(spec: &str) -> VolumeMap {
+        spec.parse().unwrap()
+    }
+
+    #[test]
+    fn maps_device_volumes_to_drives() {
+        let mut normalizer = PathNormalizer::new().with_volumes(volumes("3=C,HarddiskVolume5=d:"));
+        assert_eq!(
+            normalizer.normalize(r"\Device\HarddiskVolume3\Windows\System32\ntdll.dll"),
+            r"C:\Windows\System32\ntdll.dll"
+        );
+        assert_eq!(normalizer.normalize(r"\Device\HarddiskVolume5\Games"), r"D:\Games");
+        // 没有映射的卷保持设备路径
+        assert_eq!(
+            normalizer.normalize(r"\Device\HarddiskVolume9\a.txt"),
+            r"\Device\HarddiskVolume9\a.txt"
+        );
+    }
+
+    #[test]
+    fn rejects_malformed_volume_maps() {
+        assert!("3".parse::<VolumeMap>().is_err());
+        assert!("x=C".parse::<VolumeMap>().is_err());
+        assert!("3=CD".parse::<VolumeMap>().is_err());
+        assert_eq!(volumes(" 3=c , 4=D: ").to_string(), "3=C:,4=D:");
+    }
+
+    #[test]
+    fn expands_known_short_names_only() {
+        let mut normalizer = PathNormalizer::new();
+        assert_eq!(
+            normalizer.normalize(r"C:\PROGRA~2\COMMON~1\a.dll"),
+            r"C:\Program Files (x86)\Common Files\a.dll"
+        );
+        assert_eq!(normalizer.normalize(r"C:\Games\DELTAF~1\a.pak"), r"C:\Games\DELTAF~1\a.pak");
+    }
+
+    #[test]
+    fn expands_environment_variables_and_kernel_paths() {
+        let mut normalizer = PathNormalizer::new().with_system_drive("d");
+        assert_eq!(
+            normalizer.normalize(r"%SystemRoot%\system32\drivers\ACE-BASE.sys"),
+            r"D:\Windows\System32\drivers\ACE-BASE.sys"
+        );
+        assert_eq!(
+            normalizer.normalize(r"\SystemRoot\System32\ci.dll"),
+            r"D:\Windows\System32\ci.dll"
+        );
+        assert_eq!(
+            normalizer.normalize(r"%LOCALAPPDATA%\Temp\x.tmp"),
+            r"D:\Users\<user>\AppData\Local\Temp\x.tmp"
+        );
+        assert_eq!(normalizer.normalize(r"%UNKNOWN%\a.txt"), r"%UNKNOWN%\a.txt");
+    }
+
+    #[test]
+    fn collapses_user_names_except_shared_profiles() {
+        let mut normalizer = PathNormalizer::new();
+        assert_eq!(
+            normalizer.normalize(r"C:\Users\alice\Documents\a.txt"),
+            r"C:\Users\<user>\Documents\a.txt"
+        );
+        assert_eq!(normalizer.normalize(r"C:\Users\Public\a.txt"), r"C:\Users\Public\a.txt");
+
+        let mut keep = PathNormalizer::new().collapse_user_names(false);
+        assert_eq!(
+            keep.normalize(r"C:\Users\alice\Documents\a.txt"),
+            r"C:\Users\alice\Documents\a.txt"
+        );
+    }
+
+    #[test]
+    fn keeps_profile_variables_when_user_names_are_kept() {
+        let mut keep = PathNormalizer::new().collapse_user_names(false);
+        let paths = [r"%USERPROFILE%\a.txt", r"%AppData%\b.txt", r"%LOCALAPPDATA%\c", r"%TEMP%\d"];
+        for path in paths {
+            assert_eq!(keep.normalize(path), path);
+        }
+        assert_eq!(keep.normalize(r"%SystemRoot%\a.dll"), r"C:\Windows\a.dll");
+    }
+
+    #[test]
+    fn collapses_user_names_only_under_drive_roots() {
+        let mut normalizer = PathNormalizer::new();
+        let unc = r"\\nas\home\Users\bob\a.txt";
+        assert_eq!(normalizer.normalize(unc), unc);
+        assert_eq!(
+            normalizer.normalize(r"\Device\HarddiskVolume7\Users\bob\a.txt"),
+            r"\Device\HarddiskVolume7\Users\bob\a.txt"
+        );
+        // 映射为盘符后照常折叠
+        let mut mapped = PathNormalizer::new().with_volumes("7=E".parse().unwrap());
+        assert_eq!(
+            mapped.normalize(r"\Device\HarddiskVolume7\Users\bob\a.txt"),
+            r"E:\Users\<user>\a.txt"
+        );
+    }
+
+    #[test]
+    fn unifies_case_to_first_spelling() {
+        let mut normalizer = PathNormalizer::new();
+        let first = normalizer.normalize(r"c:\windows\SYSTEM32\Foo.dll");
+        assert_eq!(first, r"C:\Windows\System32\Foo.dll");
+        assert_eq!(normalizer.normalize(r"C:\Windows\System32\FOO.DLL"), first);
+    }
+
+    #[test]
+    fn normalize_entry_counts_merged_targets() {
+        let mut normalizer = PathNormalizer::new();
+        for target in [r"C:\Windows\a.dll", r"\\?\C:\WINDOWS\a.dll", r"C:\Windows\b.dll"] {
+            let mut entry = AceLogEntry {
+                target_file: Some(target.to_string()),
+                ..AceLogEntry::default()
+            };
+            normalizer.normalize_entry(&mut entry);
+            assert_eq!(entry.raw_target_file.as_deref(), Some(target));
+        }
+        assert_eq!(normalizer.merged_targets(), 1);
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::i18n::Locale;
+
+/// 操作类型的归类，统计与导出中使用稳定 ID（见 [`OperationKind::id`]）
+///
+/// 火绒日志中的「操作类型」是中文描述，按关键字归入以下几类；
+/// 同时命中多类时按关键字表的顺序取第一个：写操作先于查询属性匹配，
+/// 「设置文件属性」「修改属性」归为写入；查询属性先于读取匹配，「读取属性」归为查询属性。
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OperationKind {
+    /// 列举目录内容
+    Enumerate,
+    /// 仅查询文件属性、时间戳等元数据
+    QueryAttributes,
+    Delete,
+    /// 执行或加载为映像
+    Execute,
+    /// 创建、修改、重命名等写操作
+    Write,
+    Read,
+    /// 无法归类的操作类型
+    Other,
+}
+
+impl OperationKind {
+    /// 按报告中的展示顺序排列的全部操作类型
+    pub const ALL: [OperationKind; 7] = [
+        OperationKind::Read,
+        OperationKind::QueryAttributes,
+        OperationKind::Enumerate,
+        OperationKind::Write,
+        OperationKind::Delete,
+        OperationKind::Execute,
+        OperationKind::Other,
+    ];
+
+    /// 各类操作在中文描述中的关键字，顺序即匹配优先级
+    const KEYWORDS: [(OperationKind, &'static [&'static str]); 6] = [
+        (OperationKind::Enumerate, &["枚举", "遍历", "列举", "列目录", "查找文件"]),
+        (OperationKind::Delete, &["删除"]),
+        (OperationKind::Execute, &["执行", "运行", "加载", "启动"]),
+        (OperationKind::Write, &["写", "修改", "创建", "新建", "重命名", "移动", "设置"]),
+        (OperationKind::QueryAttributes, &["属性", "查询", "获取信息"]),
+        (OperationKind::Read, &["读", "打开", "访问"]),
+    ];
+
+    /// 由日志中的操作类型描述归类
+    pub fn classify(operation: &str) -> Self {
+        Self::KEYWORDS
+            .iter()
+            .find(|(_, words)| words.iter().any(|w| operation.contains(w)))
+            .map_or(OperationKind::Other, |(kind, _)| *kind)
+    }
+
+    /// 稳定 ID，用于统计键与机器可读输出
+    pub fn id(self) -> &'static str {
+        match self {
+            OperationKind::Read => "read",
+            OperationKind::QueryAttributes => "query",
+            OperationKind::Enumerate => "enumerate",
+            OperationKind::Write => "write",
+            OperationKind::Delete => "delete",
+            OperationKind::Execute => "execute",
+            OperationKind::Other => "other",
+        }
+    }
+
+    /// 操作类型的显示名
+    pub fn label(self, locale: Locale) -> &'static str {
+        let m = locale.messages();
+        match self {
+            OperationKind::Read => m.op_read,
+            OperationKind::QueryAttributes => m.op_query,
+            OperationKind::Enumerate => m.op_enumerate,
+            OperationKind::Write => m.op_write,
+            OperationKind::Delete => m.op_delete,
+            OperationKind::Execute => m.op_execute,
+            OperationKind::Other => m.op_other,
+        }
+    }
+}
+
+impl FromStr for OperationKind {
+    type Err = String;
+
+    /// 解析稳定 ID
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OperationKind::ALL
+            .into_iter()
+            .find(|kind| kind.id() == s)
+            .ok_or_else(|| format!("unknown operation kind: {}", s))
+    }
+}
+
+impl fmt::Display for OperationKind {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_huorong_operation_descriptions() {
+        let cases = [
+            ("读取文件", OperationKind::Read),
+            ("打开文件", OperationKind::Read),
+            ("读取属性", OperationKind::QueryAttributes),
+            ("查询文件属性", OperationKind::QueryAttributes),
+            ("枚举目录", OperationKind::Enumerate),
+            ("删除文件", OperationKind::Delete),
+            ("执行", OperationKind::Execute),
+            ("加载驱动", OperationKind::Execute),
+            ("写入文件", OperationKind::Write),
+            ("重命名文件", OperationKind::Write),
+            ("奇怪操作", OperationKind::Other),
+        ];
+        for (description, kind) in cases {
+            assert_eq!(OperationKind::classify(description), kind, "{}", description);
+        }
+    }
+
+    #[test]
+    fn attribute_changes_are_writes() {
+        assert_eq!(OperationKind::classify("设置文件属性"), OperationKind::Write);
+        assert_eq!(OperationKind::classify("修改属性"), OperationKind::Write);
+    }
+
+    #[test]
+    fn ids_round_trip() {
+        for kind in OperationKind::ALL {
+            assert_eq!(kind.id().parse::<OperationKind>(), Ok(kind));
+        }
+        assert!("unknown".parse::<OperationKind>().is_err());
+    }
+}
@@ -0,0 +1,154 @@
+use std::collections::HashMap;
+use std::str::FromStr;
+
+use crate::category::CategoryRules;
+use crate::i18n::Locale;
+use crate::report::DEFAULT_WIDTH;
+
+/// 风险等级
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord)]
+pub enum RiskLevel {
+    Low,
+    Medium,
+    High,
+}
+
+impl RiskLevel {
+    /// 终端/报告中使用的颜色图标
+    pub fn icon(self) -> &'static str {
+        match self {
+            RiskLevel::High => "🔴",
+            RiskLevel::Medium => "🟠",
+            RiskLevel::Low => "🟢",
+        }
+    }
+
+    /// 风险等级名称
+    pub fn label(self, locale: Locale) -> &'static str {
+        let m = locale.messages();
+        match self {
+            RiskLevel::High => m.risk_high,
+            RiskLevel::Medium => m.risk_medium,
+            RiskLevel::Low => m.risk_low,
+        }
+    }
+}
+
+/// 风险分级阈值：次数超过 `high` 为高危，超过 `medium` 为中危
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RiskThresholds {
+    pub high: usize,
+    pub medium: usize,
+}
+
+impl RiskThresholds {
+    pub const fn new(high: usize, medium: usize) -> Self {
+        RiskThresholds { high, medium }
+    }
+
+    /// 按扫描次数判定风险等级
+    pub fn level(&self, count: usize) -> RiskLevel {
+        if count > self.high {
+            RiskLevel::High
+        } else if count > self.medium {
+            RiskLevel::Medium
+        } else {
+            RiskLevel::Low
+        }
+    }
+}
+
+/// 从 `高危,中危` 形式的文本解析，如 `30,10`
+impl FromStr for RiskThresholds {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (high, medium) = s
+            .split_once(',')
+            .ok_or_else(|| {
+                format!(
+                    "阈值格式应为 `高危,中危`，如 `30,10` / expected `HIGH,MEDIUM`, e.g. `30,10`: {}",
+                    s
+                )
+            })?;
+        let parse = |v: &str| {
+            v.trim()
+                .parse::<usize>()
+                .map_err(|e| format!("无效的阈值 / invalid threshold `{}`: {}", v.trim(), e))
+        };
+        let (high, medium) = (parse(high)?, parse(medium)?);
+        if medium > high {
+            return Err(format!(
+                "中危阈值 {} 不能高于高危阈值 {} / medium threshold {} exceeds high threshold {}",
+                medium, high, medium, high
+            ));
+        }
+        Ok(RiskThresholds::new(high, medium))
+    }
+}
+
+/// 报告渲染与导出的可调参数
+#[derive(Debug, Clone)]
+pub struct ReportOptions {
+    /// 进程行为分析显示的进程数
+    pub top_processes: usize,
+    /// 高频扫描目标显示的文件数
+    pub top_files: usize,
+    /// 文件类型分布显示的扩展名数
+    pub top_extensions: usize,
+    /// 热点目录展开的最大层级（盘符为第 1 层）
+    pub tree_depth: usize,
+    /// 热点目录每层展开的子目录数
+    pub top_dirs: usize,
+    /// CSV 导出的最大行数
+    pub export_limit: usize,
+    pub process_risk: RiskThresholds,
+    pub file_risk: RiskThresholds,
+    pub category_risk: RiskThresholds,
+    /// 终端报告总宽度（列数）
+    pub width: usize,
+    /// 报告与导出使用的语言
+    pub locale: Locale,
+    /// 分类 ID 到显示名的映射，未收录的 ID 使用内置规则中的名称
+    pub category_labels: HashMap<String, String>,
+}
+
+impl Default for ReportOptions {
+    fn default() -> Self {
+        ReportOptions {
+            top_processes: 5,
+            top_files: 15,
+            top_extensions: 8,
+            tree_depth: 4,
+            top_dirs: 3,
+            export_limit: 200,
+            process_risk: RiskThresholds::new(500, 200),
+            file_risk: RiskThresholds::new(30, 10),
+            category_risk: RiskThresholds::new(1000, 300),
+            width: DEFAULT_WIDTH,
+            locale: Locale::default(),
+            category_labels: HashMap::new(),
+        }
+    }
+}
+
+impl ReportOptions {
+    /// 按 `rules` 与当前语言填入分类显示名（使用自定义分类规则时调用）
+    pub fn with_categories(mut self, rules: &CategoryRules) -> Self {
+        self.category_labels = rules
+            .labels(self.locale)
+            .into_iter()
+            .map(|(id, label)| (id.to_string(), label.to_string()))
+            .collect();
+        self
+    }
+
+    /// 分类 ID 的显示名
+    pub fn category_label<'a>(&'a self, id: &'a str) -> &'a str {
+        self.category_labels
+            .get(id)
+            .map(String::as_str)
+            .or_else(|| CategoryRules::builtin().label(id, self.locale))
+            .unwrap_or(id)
+    }
+}
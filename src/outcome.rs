@@ -0,0 +1,159 @@
+use std::fmt;
+use std::str::FromStr;
+
+use crate::i18n::Locale;
+
+/// 火绒对一次操作的处理结果，统计与导出中使用稳定 ID（见 [`OperationOutcome::id`]）
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum OperationOutcome {
+    /// 按规则直接阻止
+    Blocked,
+    /// 按规则直接放行
+    Allowed,
+    /// 弹窗询问后用户选择允许
+    AskedAllowed,
+    /// 弹窗询问后用户选择阻止
+    AskedDenied,
+    /// 日志中没有操作结果或无法识别
+    Unknown,
+}
+
+impl OperationOutcome {
+    /// 按报告中的展示顺序排列的全部结果
+    pub const ALL: [OperationOutcome; 5] = [
+        OperationOutcome::Blocked,
+        OperationOutcome::AskedDenied,
+        OperationOutcome::Allowed,
+        OperationOutcome::AskedAllowed,
+        OperationOutcome::Unknown,
+    ];
+
+    const DENY_WORDS: [&'static str; 4] = ["阻止", "拦截", "禁止", "拒绝"];
+    const ALLOW_WORDS: [&'static str; 3] = ["放行", "允许", "通过"];
+    const ASK_WORDS: [&'static str; 2] = ["询问", "用户"];
+    /// 紧挨在放行词之前时表示否定，如 `不允许`、`未放行`
+    const NEGATION_WORDS: [&'static str; 4] = ["不", "未", "没有", "无法"];
+
+    /// 由日志中的操作结果描述（如 `已阻止`、`询问后允许`）归类
+    ///
+    /// 被否定的放行词（如 `不允许`、`未通过`）按阻止处理。
+    pub fn classify(result: &str) -> Self {
+        let has = |words: &[&str]| words.iter().any(|w| result.contains(w));
+        let asked = has(&Self::ASK_WORDS);
+        if has(&Self::DENY_WORDS) || Self::has_negated_allow(result) {
+            if asked {
+                OperationOutcome::AskedDenied
+            } else {
+                OperationOutcome::Blocked
+            }
+        } else if has(&Self::ALLOW_WORDS) {
+            if asked {
+                OperationOutcome::AskedAllowed
+            } else {
+                OperationOutcome::Allowed
+            }
+        } else {
+            OperationOutcome::Unknown
+        }
+    }
+
+    /// 是否含有被否定的放行词
+    fn has_negated_allow(result: &str) -> bool {
+        Self::ALLOW_WORDS.iter().any(|word| {
+            result.match_indices(word).any(|(start, _)| {
+                let before = &result[..start];
+                Self::NEGATION_WORDS.iter().any(|n| before.ends_with(n))
+            })
+        })
+    }
+
+    /// 稳定 ID，用于统计键与机器可读输出
+    pub fn id(self) -> &'static str {
+        match self {
+            OperationOutcome::Blocked => "blocked",
+            OperationOutcome::Allowed => "allowed",
+            OperationOutcome::AskedAllowed => "asked_allowed",
+            OperationOutcome::AskedDenied => "asked_denied",
+            OperationOutcome::Unknown => "unknown",
+        }
+    }
+
+    /// 操作最终被阻止（含询问后阻止）
+    pub fn is_blocked(self) -> bool {
+        matches!(self, OperationOutcome::Blocked | OperationOutcome::AskedDenied)
+    }
+
+    /// 操作最终被放行（含询问后允许），即没有被当前规则拦住
+    pub fn is_allowed(self) -> bool {
+        matches!(self, OperationOutcome::Allowed | OperationOutcome::AskedAllowed)
+    }
+
+    /// 结果的显示名
+    pub fn label(self, locale: Locale) -> &'static str {
+        let m = locale.messages();
+        match self {
+            OperationOutcome::Blocked => m.outcome_blocked,
+            OperationOutcome::Allowed => m.outcome_allowed,
+            OperationOutcome::AskedAllowed => m.outcome_asked_allowed,
+            OperationOutcome::AskedDenied => m.outcome_asked_denied,
+            OperationOutcome::Unknown => m.outcome_unknown,
+        }
+    }
+}
+
+impl FromStr for OperationOutcome {
+    type Err = String;
+
+    /// 解析稳定 ID
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        OperationOutcome::ALL
+            .into_iter()
+            .find(|outcome| outcome.id() == s)
+            .ok_or_else(|| format!("unknown operation outcome: {}", s))
+    }
+}
+
+impl fmt::Display for OperationOutcome {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(self.id())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn classifies_huorong_results() {
+        let cases = [
+            ("已阻止", OperationOutcome::Blocked),
+            ("拦截", OperationOutcome::Blocked),
+            ("已放行", OperationOutcome::Allowed),
+            ("允许", OperationOutcome::Allowed),
+            ("询问后允许", OperationOutcome::AskedAllowed),
+            ("用户选择阻止", OperationOutcome::AskedDenied),
+            ("", OperationOutcome::Unknown),
+            ("已记录", OperationOutcome::Unknown),
+        ];
+        for (result, outcome) in cases {
+            assert_eq!(OperationOutcome::classify(result), outcome, "{}", result);
+        }
+    }
+
+    #[test]
+    fn negated_allow_is_blocked() {
+        for result in ["不允许", "未允许", "未放行", "不通过", "没有允许"] {
+            assert_eq!(OperationOutcome::classify(result), OperationOutcome::Blocked, "{}", result);
+        }
+        assert_eq!(OperationOutcome::classify("询问后不允许"), OperationOutcome::AskedDenied);
+        assert_eq!(OperationOutcome::classify("用户未放行"), OperationOutcome::AskedDenied);
+    }
+
+    #[test]
+    fn ids_round_trip() {
+        for outcome in OperationOutcome::ALL {
+            assert_eq!(outcome.id().parse::<OperationOutcome>(), Ok(outcome));
+        }
+        assert!("denied".parse::<OperationOutcome>().is_err());
+    }
+}
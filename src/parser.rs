@@ -0,0 +1,39 @@
+use crate::entry::AceLogEntry;
+use crate::stats::AceScanStats;
+
+/// 火绒日志中条目之间的分隔行（60 个 `>`）
+pub const ENTRY_SEPARATOR: &str = ">>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>>";
+
+/// 逐条产出 [`AceLogEntry`] 的日志解析迭代器，只保留 SGuard 相关的文件操作条目
+pub struct AceLogParser<'a> {
+    entries: std::str::Split<'a, &'static str>,
+}
+
+impl<'a> AceLogParser<'a> {
+    pub fn new(logs: &'a str) -> Self {
+        AceLogParser {
+            entries: logs.split(ENTRY_SEPARATOR),
+        }
+    }
+}
+
+impl Iterator for AceLogParser<'_> {
+    type Item = AceLogEntry;
+
+    fn next(&mut self) -> Option<AceLogEntry> {
+        self.entries
+            .by_ref()
+            .find(|e| is_ace_entry(e))
+            .map(AceLogEntry::parse)
+    }
+}
+
+/// 是否为 SGuard 相关的文件操作条目
+pub(crate) fn is_ace_entry(entry: &str) -> bool {
+    !entry.trim().is_empty() && entry.contains("SGuard") && entry.contains("操作文件：")
+}
+
+/// 解析整份日志文本并直接汇总为统计结果
+pub fn parse_ace_logs_precise(logs: &str) -> AceScanStats {
+    AceScanStats::from_entries(AceLogParser::new(logs))
+}
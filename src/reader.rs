@@ -0,0 +1,252 @@
+use std::collections::VecDeque;
+use std::fs::File;
+use std::io::{self, BufRead, BufReader, Cursor, Read};
+use std::path::Path;
+
+use encoding_rs_io::DecodeReaderBytesBuilder;
+
+use crate::encoding::{detect_encoding, TextEncoding};
+use crate::entry::AceLogEntry;
+use crate::parser::{is_ace_entry, ENTRY_SEPARATOR};
+
+/// 格式检测时读取的文件头部字节数
+pub const SNIFF_LEN: usize = 16 * 1024;
+
+/// 已打开的日志文件：只预读头部做编码与格式检测，条目按需流式读取
+pub struct LogFile {
+    encoding: TextEncoding,
+    head_matches: bool,
+    reader: Box<dyn BufRead>,
+}
+
+impl LogFile {
+    /// 打开日志文件
+    pub fn open(path: &Path) -> io::Result<Self> {
+        Self::from_reader(File::open(path)?)
+    }
+
+    /// 从任意字节流构造，预读 [`SNIFF_LEN`] 字节用于编码与格式检测
+    pub fn from_reader<R: Read + 'static>(mut inner: R) -> io::Result<Self> {
+        let mut head = Vec::with_capacity(SNIFF_LEN);
+        inner.by_ref().take(SNIFF_LEN as u64).read_to_end(&mut head)?;
+
+        let encoding = detect_encoding(&head);
+        let (head_text, _, _) = encoding.encoding().decode(&head);
+        let head_matches = looks_like_huorong_log(&head_text);
+
+        let raw = Cursor::new(head).chain(inner);
+        let reader: Box<dyn BufRead> = match encoding {
+            TextEncoding::Utf8 => Box::new(BufReader::new(raw)),
+            // 其余编码统一转码为 UTF-8（同时去掉 BOM）
+            _ => Box::new(BufReader::new(
+                DecodeReaderBytesBuilder::new()
+                    .encoding(Some(encoding.encoding()))
+                    .build(raw),
+            )),
+        };
+
+        Ok(LogFile {
+            encoding,
+            head_matches,
+            reader,
+        })
+    }
+
+    /// 检测到的文本编码
+    pub fn encoding(&self) -> TextEncoding {
+        self.encoding
+    }
+
+    /// 文件头部是否具有火绒安全日志的特征
+    pub fn is_huorong_log(&self) -> bool {
+        self.head_matches
+    }
+
+    /// 逐条读取 ACE 扫盘日志条目
+    pub fn entries(self) -> AceLogReader<Box<dyn BufRead>> {
+        AceLogReader::new(self.reader)
+    }
+}
+
+/// 检测是否为火绒安全日志（只检查文件头部的格式特征）
+pub fn is_huorong_log(path: &Path) -> io::Result<bool> {
+    LogFile::open(path).map(|log| log.is_huorong_log())
+}
+
+fn looks_like_huorong_log(head: &str) -> bool {
+    head.contains("操作文件：") && head.contains("触犯自定义防护规则")
+}
+
+/// 从任意 [`BufRead`] 增量读取日志的迭代器，内存占用以单个条目为上限
+pub struct AceLogReader<R> {
+    reader: R,
+    line: Vec<u8>,
+    current: String,
+    ready: VecDeque<String>,
+    eof: bool,
+}
+
+impl<R: BufRead> AceLogReader<R> {
+    pub fn new(reader: R) -> Self {
+        AceLogReader {
+            reader,
+            line: Vec::new(),
+            current: String::new(),
+            ready: VecDeque::new(),
+            eof: false,
+        }
+    }
+
+    /// 读取下一个原始条目文本（不做 ACE 条目筛选）
+    fn next_raw(&mut self) -> io::Result<Option<String>> {
+        while self.ready.is_empty() && !self.eof {
+            self.line.clear();
+            if self.reader.read_until(b'\n', &mut self.line)? == 0 {
+                self.eof = true;
+                if !self.current.is_empty() {
+                    self.ready.push_back(std::mem::take(&mut self.current));
+                }
+                break;
+            }
+
+            let line = std::str::from_utf8(&self.line)
+                .map_err(|e| io::Error::new(io::ErrorKind::InvalidData, e))?;
+            let mut pieces = line.split(ENTRY_SEPARATOR);
+            self.current.push_str(pieces.next().unwrap_or_default());
+            // 行内每出现一次分隔符，就结束一个条目
+            for piece in pieces {
+                self.ready.push_back(std::mem::take(&mut self.current));
+                self.current.push_str(piece);
+            }
+        }
+        Ok(self.ready.pop_front())
+    }
+}
+
+impl<R: BufRead> Iterator for AceLogReader<R> {
+    type Item = io::Result<AceLogEntry>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        loop {
+            match self.next_raw() {
+                Ok(Some(raw)) if is_ace_entry(&raw) => return Some(Ok(AceLogEntry::parse(&raw))),
+                Ok(Some(_)) => continue,
+                Ok(None) => return None,
+                Err(e) => return Some(Err(e)),
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parser::AceLogParser;
+
+    use encoding_rs::GB18030;
+
+    /// 一个火绒「触犯自定义防护规则」条目，`n` 区分时间与扫描目标
+    fn entry(n: u32) -> String {
+        format!(
+            "2024-05-01 12:00:{:02} 火绒\n触犯自定义防护规则\n操作进程：C:\\Program Files\\AntiCheatExpert\\SGuard64.exe\n\
+             触犯规则：ACE\n操作类型：读取文件\n操作文件：C:\\Windows\\f{}.dll\n操作结果：已阻止\n",
+            n, n
+        )
+    }
+
+    fn log(entries: &[String]) -> String {
+        entries.join(&format!("{}\n", ENTRY_SEPARATOR))
+    }
+
+    fn read(text: &str) -> Vec<AceLogEntry> {
+        AceLogReader::new(text.as_bytes())
+            .collect::<io::Result<_>>()
+            .unwrap()
+    }
+
+    fn targets(entries: &[AceLogEntry]) -> Vec<&str> {
+        entries.iter().filter_map(|e| e.target_file.as_deref()).collect()
+    }
+
+    #[test]
+    fn splits_on_separator_in_the_middle_of_a_line() {
+        let text = format!("{}{}{}", entry(1).trim_end(), ENTRY_SEPARATOR, entry(2));
+        assert_eq!(targets(&read(&text)), [r"C:\Windows\f1.dll", r"C:\Windows\f2.dll"]);
+    }
+
+    #[test]
+    fn skips_empty_entries_between_adjacent_separators() {
+        let text = format!("{}{}{}\n{}", entry(1), ENTRY_SEPARATOR, ENTRY_SEPARATOR, entry(2));
+        assert_eq!(read(&text).len(), 2);
+        let text = format!("{}{}\n{}\n{}", entry(1), ENTRY_SEPARATOR, ENTRY_SEPARATOR, entry(2));
+        assert_eq!(read(&text).len(), 2);
+    }
+
+    #[test]
+    fn keeps_last_entry_without_trailing_separator() {
+        let text = log(&[entry(1), entry(2).trim_end().to_string()]);
+        let entries = read(&text);
+        assert_eq!(entries.len(), 2);
+        assert_eq!(entries[1].result.as_deref(), Some("已阻止"));
+    }
+
+    #[test]
+    fn parses_crlf_line_endings() {
+        let text = log(&[entry(1), entry(2)]).replace('\n', "\r\n");
+        let entries = read(&text);
+        assert_eq!(targets(&entries), [r"C:\Windows\f1.dll", r"C:\Windows\f2.dll"]);
+        assert_eq!(entries[0].result.as_deref(), Some("已阻止"));
+        assert!(entries[0].timestamp.is_some());
+    }
+
+    #[test]
+    fn matches_the_in_memory_parser() {
+        let texts = [
+            log(&[entry(1), "无关的条目\n".to_string(), entry(2), entry(3)]),
+            format!("{}{}{}", entry(1), ENTRY_SEPARATOR, entry(2)),
+            log(&[entry(1), entry(2)]).replace('\n', "\r\n"),
+        ];
+        for text in &texts {
+            assert_eq!(read(text), AceLogParser::new(text).collect::<Vec<_>>());
+        }
+    }
+
+    fn decode_all(bytes: Vec<u8>) -> (TextEncoding, bool, Vec<AceLogEntry>) {
+        let log = LogFile::from_reader(Cursor::new(bytes)).unwrap();
+        let (encoding, huorong) = (log.encoding(), log.is_huorong_log());
+        let entries = log.entries().collect::<io::Result<_>>().unwrap();
+        (encoding, huorong, entries)
+    }
+
+    /// 超过 [`SNIFF_LEN`] 的日志，确保头部之后的内容也被完整转码
+    fn long_log() -> String {
+        log(&(0..400).map(|n| entry(n % 60)).collect::<Vec<_>>())
+    }
+
+    #[test]
+    fn decodes_gbk_logs_to_the_end() {
+        let text = long_log();
+        assert!(text.len() > SNIFF_LEN);
+        let (gbk, _, _) = GB18030.encode(&text);
+        let (encoding, huorong, entries) = decode_all(gbk.into_owned());
+        assert_eq!(encoding, TextEncoding::Gb18030);
+        assert!(huorong);
+        assert_eq!(entries, read(&text));
+    }
+
+    #[test]
+    fn decodes_utf16le_logs_with_and_without_bom() {
+        let text = long_log();
+        let utf16: Vec<u8> = text.encode_utf16().flat_map(u16::to_le_bytes).collect();
+        let mut with_bom = vec![0xFF, 0xFE];
+        with_bom.extend_from_slice(&utf16);
+
+        for bytes in [with_bom, utf16] {
+            let (encoding, huorong, entries) = decode_all(bytes);
+            assert_eq!(encoding, TextEncoding::Utf16Le);
+            assert!(huorong);
+            assert_eq!(entries.len(), 400);
+            assert_eq!(entries, read(&text));
+        }
+    }
+}
@@ -0,0 +1,496 @@
+use std::fmt::{self, Write};
+
+use console::Term;
+use unicode_segmentation::UnicodeSegmentation;
+use unicode_width::UnicodeWidthStr;
+
+use crate::diff::StatsDiff;
+use crate::i18n::Messages;
+use crate::model::{Block, ColumnKind, Metric, ReportModel, Table};
+use crate::options::ReportOptions;
+use crate::session::ScanSession;
+use crate::stats::AceScanStats;
+
+/// 未连接终端（重定向、写入文件）时的报告总宽度
+pub const DEFAULT_WIDTH: usize = 76;
+/// 自适应排版的宽度下限与上限，过宽时表格难以阅读
+const MIN_WIDTH: usize = 60;
+const MAX_WIDTH: usize = 200;
+/// 柱状图最短柱宽
+const MIN_BAR_WIDTH: usize = 10;
+/// 路径列的最小宽度
+const MIN_PATH_WIDTH: usize = 16;
+
+/// 当前终端的列数（限制在合理范围内）；标准输出未连接终端时返回 [`DEFAULT_WIDTH`]
+pub fn terminal_width() -> usize {
+    Term::stdout()
+        .size_checked()
+        .map_or(DEFAULT_WIDTH, |(_, cols)| clamp_width(usize::from(cols)))
+}
+
+/// 将报告宽度限制在自适应排版支持的范围内
+pub fn clamp_width(width: usize) -> usize {
+    width.clamp(MIN_WIDTH, MAX_WIDTH)
+}
+
+/// 计算字符串在等宽终端中的显示宽度
+///
+/// 按字素簇（用户感知的单个字符）计算：东亚宽字符与 emoji 占 2 列，组合符号与零宽字符不占列，
+/// 带 VS16（`U+FE0F`）的 emoji 与 ZWJ 组合 emoji 整体占 2 列。
+pub fn display_width(s: &str) -> usize {
+    s.graphemes(true).map(grapheme_width).sum()
+}
+
+/// 单个字素簇的显示宽度
+fn grapheme_width(g: &str) -> usize {
+    let width = g.width();
+    if width == 0 {
+        // 孤立的组合符号等，多数终端不为其占列
+        return 0;
+    }
+    if g.contains('\u{FE0F}') || g.contains('\u{200D}') {
+        // emoji 表现形式与 ZWJ 组合序列按一个宽字符显示
+        return 2;
+    }
+    width.min(2)
+}
+
+/// 截断或填充字符串到指定显示宽度，截断时以 `…` 结尾且不会切开字素簇
+pub fn pad_to_width(s: &str, width: usize) -> String {
+    let current_width = display_width(s);
+    if width == 0 {
+        // 连省略号也放不下
+        return String::new();
+    }
+    if current_width > width {
+        let budget = width.saturating_sub(1);
+        let mut result = String::new();
+        let mut current = 0;
+        for g in s.graphemes(true) {
+            let w = grapheme_width(g);
+            if current + w > budget {
+                break;
+            }
+            result.push_str(g);
+            current += w;
+        }
+        result.push('…');
+        current += 1;
+        // 宽字符放不下时用空格补足，保证列宽准确
+        result.push_str(&" ".repeat(width.saturating_sub(current)));
+        result
+    } else {
+        format!("{}{}", s, " ".repeat(width - current_width))
+    }
+}
+
+/// 省略过长路径的中间部分，保留开头（盘符/顶层目录）与结尾（文件名）
+pub fn elide_middle(s: &str, width: usize) -> String {
+    if display_width(s) <= width {
+        return s.to_string();
+    }
+    const ELLIPSIS: &str = "...";
+    if width < ELLIPSIS.len() {
+        return pad_to_width(s, width);
+    }
+    let budget = width.saturating_sub(ELLIPSIS.len());
+    let prefix_budget = budget * 2 / 5;
+    let suffix_budget = budget - prefix_budget;
+
+    let mut prefix = String::new();
+    let mut used = 0;
+    for g in s.graphemes(true) {
+        let w = grapheme_width(g);
+        if used + w > prefix_budget {
+            break;
+        }
+        prefix.push_str(g);
+        used += w;
+    }
+
+    let mut suffix: Vec<&str> = Vec::new();
+    let mut used = 0;
+    for g in s.graphemes(true).rev() {
+        let w = grapheme_width(g);
+        if used + w > suffix_budget {
+            break;
+        }
+        suffix.push(g);
+        used += w;
+    }
+    let suffix: String = suffix.into_iter().rev().collect();
+    format!("{}{}{}", prefix, ELLIPSIS, suffix)
+}
+
+/// 生成终端版详细分析报告文本
+pub fn render_detailed_report(
+    stats: &AceScanStats,
+    sessions: &[ScanSession],
+    options: &ReportOptions,
+) -> String {
+    let mut out = String::new();
+    write_detailed_report(&mut out, stats, sessions, options).expect("写入 String 不会失败");
+    out
+}
+
+/// 将终端版详细分析报告写入 `out`
+pub fn write_detailed_report<W: Write>(
+    out: &mut W,
+    stats: &AceScanStats,
+    sessions: &[ScanSession],
+    options: &ReportOptions,
+) -> fmt::Result {
+    write_terminal(out, &ReportModel::detailed(stats, sessions, options), options.width)
+}
+
+/// 生成触犯规则统计文本
+pub fn render_rules_report(stats: &AceScanStats, options: &ReportOptions) -> String {
+    let mut out = String::new();
+    write_rules_report(&mut out, stats, options).expect("写入 String 不会失败");
+    out
+}
+
+/// 将触犯的火绒规则统计写入 `out`
+pub fn write_rules_report<W: Write>(
+    out: &mut W,
+    stats: &AceScanStats,
+    options: &ReportOptions,
+) -> fmt::Result {
+    write_terminal(out, &ReportModel::rules(stats, options), options.width)
+}
+
+/// 生成游戏会话分析文本
+pub fn render_sessions_report(sessions: &[ScanSession], options: &ReportOptions) -> String {
+    let mut out = String::new();
+    write_sessions_report(&mut out, sessions, options).expect("写入 String 不会失败");
+    out
+}
+
+/// 将游戏会话分析写入 `out`
+pub fn write_sessions_report<W: Write>(
+    out: &mut W,
+    sessions: &[ScanSession],
+    options: &ReportOptions,
+) -> fmt::Result {
+    write_terminal(out, &ReportModel::sessions(sessions, options), options.width)
+}
+
+/// 生成两次统计的对比报告文本
+pub fn render_diff_report(
+    diff: &StatsDiff,
+    before: &str,
+    after: &str,
+    options: &ReportOptions,
+) -> String {
+    let mut out = String::new();
+    write_diff_report(&mut out, diff, before, after, options).expect("写入 String 不会失败");
+    out
+}
+
+/// 将两次统计的对比报告写入 `out`，`before`/`after` 为两份输入的名称
+pub fn write_diff_report<W: Write>(
+    out: &mut W,
+    diff: &StatsDiff,
+    before: &str,
+    after: &str,
+    options: &ReportOptions,
+) -> fmt::Result {
+    write_terminal(out, &ReportModel::diff(diff, before, after, options), options.width)
+}
+
+/// 将任意报告模型渲染为终端文本
+pub fn render_terminal(model: &ReportModel, width: usize) -> String {
+    let mut out = String::new();
+    write_terminal(&mut out, model, width).expect("写入 String 不会失败");
+    out
+}
+
+/// 以终端排版输出报告模型，路径列与柱状图随 `width` 伸缩
+pub fn write_terminal<W: Write>(out: &mut W, model: &ReportModel, width: usize) -> fmt::Result {
+    if let Some(title) = &model.title {
+        writeln!(out, "\n{}", "=".repeat(width))?;
+        writeln!(out, "{}", center(title, width))?;
+        if let Some(subtitle) = &model.subtitle {
+            writeln!(out, "{}", center(subtitle, width))?;
+        }
+        writeln!(out, "{}", "=".repeat(width))?;
+    }
+
+    for section in &model.sections {
+        writeln!(out, "\n「{}」", section.title)?;
+        // 目录树只在 HTML 中绘制，终端以同节的热点目录表代替
+        let blocks = section.blocks.iter().filter(|b| !matches!(b, Block::Tree(_)));
+        for (i, block) in blocks.enumerate() {
+            if i > 0 {
+                writeln!(out)?;
+            }
+            match block {
+                Block::Metrics(metrics) => write_metrics(out, metrics)?,
+                Block::Table(table) => write_table(out, table, width)?,
+                Block::Bars { header, bars } => {
+                    write_bars(out, header, bars, width, model.locale.messages())?
+                }
+                Block::Heatmap(rows) => write_heatmap(out, rows, model.locale.messages())?,
+                Block::List(items) => write_list(out, items)?,
+                Block::Tree(_) => {}
+            }
+        }
+    }
+
+    if model.title.is_some() {
+        writeln!(out, "\n{}", "=".repeat(width))?;
+    }
+    Ok(())
+}
+
+fn center(s: &str, width: usize) -> String {
+    let left = width.saturating_sub(display_width(s)) / 2;
+    format!("{}{}", " ".repeat(left), s)
+}
+
+fn write_metrics<W: Write>(out: &mut W, metrics: &[Metric]) -> fmt::Result {
+    let label_width = metrics.iter().map(|m| display_width(&m.label)).max().unwrap_or(0);
+    for metric in metrics {
+        let label = pad_to_width(&format!("{}:", metric.label), label_width + 1);
+        match &metric.note {
+            Some(note) => writeln!(out, "  • {} {} ({})", label, pad_left(&metric.value, 10), note)?,
+            None => writeln!(out, "  • {} {}", label, pad_left(&metric.value, 10))?,
+        }
+    }
+    Ok(())
+}
+
+fn write_table<W: Write>(out: &mut W, table: &Table, width: usize) -> fmt::Result {
+    const GAP: &str = "  ";
+    let mut widths: Vec<usize> = table.columns.iter().map(|c| display_width(&c.header)).collect();
+    for row in table.all_rows() {
+        for (w, cell) in widths.iter_mut().zip(row) {
+            *w = (*w).max(display_width(cell));
+        }
+    }
+
+    // 路径列占用其余列排版后剩下的宽度，有多个路径列时由较短的列先取所需，余下的平分
+    let mut path_cols: Vec<usize> = (0..widths.len())
+        .filter(|&i| table.columns[i].kind == ColumnKind::Path)
+        .collect();
+    if !path_cols.is_empty() {
+        let fixed: usize = widths
+            .iter()
+            .enumerate()
+            .filter(|(i, _)| !path_cols.contains(i))
+            .map(|(_, w)| w)
+            .sum();
+        let mut available = width.saturating_sub(2 + fixed + GAP.len() * (widths.len() - 1));
+        path_cols.sort_by_key(|&i| widths[i]);
+        for (n, &col) in path_cols.iter().enumerate() {
+            let share = (available / (path_cols.len() - n)).max(MIN_PATH_WIDTH);
+            widths[col] = widths[col].min(share);
+            available = available.saturating_sub(widths[col]);
+        }
+    }
+
+    let format_row = |cells: &mut dyn Iterator<Item = (&str, ColumnKind)>| {
+        let line: Vec<String> = cells
+            .zip(&widths)
+            .map(|((cell, kind), &width)| align(cell, kind, width))
+            .collect();
+        format!("  {}", line.join(GAP)).trim_end().to_string()
+    };
+
+    let header = format_row(&mut table.columns.iter().map(|c| (c.header.as_str(), c.kind)));
+    writeln!(out, "{}", header)?;
+    let total: usize = widths.iter().sum::<usize>() + GAP.len() * (widths.len() - 1);
+    writeln!(out, "  {}", "-".repeat(total))?;
+    for row in table.all_rows() {
+        let line = format_row(
+            &mut row
+                .iter()
+                .map(String::as_str)
+                .zip(table.columns.iter().map(|c| c.kind)),
+        );
+        writeln!(out, "{}", line)?;
+    }
+    Ok(())
+}
+
+fn align(cell: &str, kind: ColumnKind, width: usize) -> String {
+    match kind {
+        ColumnKind::Number => pad_left(cell, width),
+        ColumnKind::Path => pad_to_width(&elide_middle(cell, width), width),
+        ColumnKind::Text => pad_to_width(cell, width),
+    }
+}
+
+/// 左侧补空格右对齐到指定显示宽度
+pub(crate) fn pad_left(s: &str, width: usize) -> String {
+    format!("{}{}", " ".repeat(width.saturating_sub(display_width(s))), s)
+}
+
+fn write_bars<W: Write>(
+    out: &mut W,
+    header: &str,
+    bars: &[(String, usize)],
+    width: usize,
+    m: &Messages,
+) -> fmt::Result {
+    let peak = bars.iter().map(|(_, c)| *c).max().unwrap_or(0).max(1);
+    let label_width = bars
+        .iter()
+        .map(|(l, _)| display_width(l))
+        .chain([display_width(header)])
+        .max()
+        .unwrap_or(0);
+    writeln!(
+        out,
+        "  {} {} {}",
+        pad_to_width(header, label_width),
+        pad_left(m.col_count, 6),
+        m.col_distribution
+    )?;
+    // 行首缩进 2 + 标签 + 空格 + 6 位计数 + 空格
+    let max_bar = width.saturating_sub(label_width + 10).max(MIN_BAR_WIDTH);
+    for (label, count) in bars {
+        let bar_width = (*count as f64 / peak as f64 * max_bar as f64).round() as usize;
+        writeln!(
+            out,
+            "  {} {:>6} {}",
+            pad_to_width(label, label_width),
+            count,
+            "█".repeat(bar_width)
+        )?;
+    }
+    Ok(())
+}
+
+fn write_heatmap<W: Write>(
+    out: &mut W,
+    rows: &[(String, [usize; 24])],
+    m: &Messages,
+) -> fmt::Result {
+    let label_width = rows.iter().map(|(l, _)| display_width(l)).max().unwrap_or(0);
+    writeln!(out, "  {}  {}  {}", " ".repeat(label_width), hour_axis(), pad_left(m.total, 6))?;
+    let peak = rows
+        .iter()
+        .flat_map(|(_, hours)| hours.iter().copied())
+        .max()
+        .unwrap_or(1);
+    for (label, hours) in rows {
+        let cells: String = hours.iter().map(|&c| heat_cell(c, peak)).collect();
+        let total: usize = hours.iter().sum();
+        writeln!(out, "  {}  {}  {:>6}", pad_to_width(label, label_width), cells, total)?;
+    }
+    writeln!(out, "  {}", m.heatmap_legend)
+}
+
+fn write_list<W: Write>(out: &mut W, items: &[String]) -> fmt::Result {
+    for (i, item) in items.iter().enumerate() {
+        for (j, line) in item.lines().enumerate() {
+            if j == 0 {
+                writeln!(out, "  {}. {}", i + 1, line)?;
+            } else {
+                writeln!(out, "     {}", line)?;
+            }
+        }
+    }
+    Ok(())
+}
+
+/// 日期 × 小时矩阵的表头刻度（每小时占一列）
+pub(crate) fn hour_axis() -> String {
+    let mut axis = [' '; 24];
+    for (pos, label) in [(0, "0"), (6, "6"), (12, "12"), (18, "18"), (22, "23")] {
+        for (i, c) in label.chars().enumerate() {
+            axis[pos + i] = c;
+        }
+    }
+    axis.iter().collect()
+}
+
+/// 按相对峰值的比例选取热力字符
+pub(crate) fn heat_cell(count: usize, peak: usize) -> char {
+    const LEVELS: [char; 4] = ['░', '▒', '▓', '█'];
+    if count == 0 || peak == 0 {
+        return '·';
+    }
+    let level = (count * LEVELS.len()).div_ceil(peak).clamp(1, LEVELS.len());
+    LEVELS[level - 1]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn measures_wide_and_zero_width_characters() {
+        assert_eq!(display_width("SGuard64.exe"), 12);
+        assert_eq!(display_width("反作弊组件"), 10);
+        assert_eq!(display_width("扫描，拦截！"), 12);
+        assert_eq!(display_width("cafe\u{301}"), 4);
+        assert_eq!(display_width("\u{301}"), 0);
+        assert_eq!(display_width("❤\u{FE0F}"), 2);
+        assert_eq!(display_width("👨\u{200D}👩\u{200D}👧"), 2);
+        assert_eq!(display_width("🎮 会话"), 7);
+    }
+
+    #[test]
+    fn pads_and_truncates_to_exact_width() {
+        assert_eq!(pad_to_width("ab", 4), "ab  ");
+        assert_eq!(pad_to_width("abcdef", 4), "abc…");
+        // 宽字符放不下时以空格补足
+        assert_eq!(pad_to_width("反作弊组件", 6), "反作… ");
+        assert_eq!(pad_to_width("反作弊组件", 5), "反作…");
+        assert_eq!(display_width(&pad_to_width("反作弊组件", 5)), 5);
+        assert_eq!(pad_to_width("反作弊组件", 1), "…");
+        assert_eq!(pad_to_width("反作弊组件", 0), "");
+        assert_eq!(pad_to_width("", 0), "");
+    }
+
+    #[test]
+    fn truncation_keeps_grapheme_clusters_whole() {
+        let family = "👨\u{200D}👩\u{200D}👧";
+        assert_eq!(pad_to_width(&format!("{}{}x", family, family), 4), format!("{}… ", family));
+        assert_eq!(pad_to_width("e\u{301}e\u{301}e\u{301}", 2), "e\u{301}…");
+        assert_eq!(pad_to_width("❤\u{FE0F}❤\u{FE0F}", 3), "❤\u{FE0F}…");
+        for width in 0..12 {
+            let padded = pad_to_width("a反👨\u{200D}👩e\u{301}❤\u{FE0F}b", width);
+            assert_eq!(display_width(&padded), width, "{:?}", padded);
+        }
+    }
+
+    #[test]
+    fn bar_charts_start_with_a_header_line() {
+        use crate::i18n::Locale;
+        use crate::model::Section;
+
+        let model = ReportModel {
+            locale: Locale::En,
+            sections: vec![Section {
+                title: "Daily".to_string(),
+                blocks: vec![Block::Bars {
+                    header: "Date".to_string(),
+                    bars: vec![("2024-05-01".to_string(), 4), ("2024-05-02".to_string(), 2)],
+                }],
+            }],
+            ..ReportModel::default()
+        };
+        let text = render_terminal(&model, 80);
+        let lines: Vec<_> = text.lines().skip_while(|l| !l.contains("Daily")).skip(1).collect();
+        assert_eq!(lines[0], "  Date        Count Distribution");
+        assert!(lines[1].starts_with("  2024-05-01      4 █"));
+    }
+
+    #[test]
+    fn elides_the_middle_of_long_paths() {
+        let path = r"C:\Program Files\AntiCheatExpert\SGuard64.exe";
+        assert_eq!(elide_middle(path, 60), path);
+        let elided = elide_middle(path, 20);
+        assert_eq!(elided, r"C:\Pro...Guard64.exe");
+        assert!(display_width(&elided) <= 20);
+
+        let wide = r"D:\游戏\三角洲行动\反作弊\组件.sys";
+        for width in 0..display_width(wide) {
+            let elided = elide_middle(wide, width);
+            assert!(display_width(&elided) <= width, "{} > {}", elided, width);
+        }
+    }
+}
@@ -0,0 +1,260 @@
+use std::collections::HashMap;
+
+use chrono::{NaiveDateTime, TimeDelta};
+use serde::{Deserialize, Serialize};
+
+use crate::entry::AceLogEntry;
+
+/// 默认会话间隔：相邻两条日志相隔超过该时长即视为新一次游戏会话
+pub const DEFAULT_SESSION_GAP: TimeDelta = TimeDelta::minutes(10);
+
+/// 一次游戏会话内的扫盘行为（由时间上连续的一簇日志组成）
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct ScanSession {
+    pub start: NaiveDateTime,
+    pub end: NaiveDateTime,
+    pub event_count: usize,
+    pub blocked_count: usize,
+    /// 会话内各目标文件的扫描次数
+    pub targets: HashMap<String, usize>,
+}
+
+impl ScanSession {
+    fn new(start: NaiveDateTime) -> Self {
+        ScanSession {
+            start,
+            end: start,
+            event_count: 0,
+            blocked_count: 0,
+            targets: HashMap::new(),
+        }
+    }
+
+    /// 并入紧随其后的会话
+    fn absorb(&mut self, later: ScanSession) {
+        self.end = self.end.max(later.end);
+        self.event_count += later.event_count;
+        self.blocked_count += later.blocked_count;
+        for (target, count) in later.targets {
+            *self.targets.entry(target).or_insert(0) += count;
+        }
+    }
+
+    /// 会话持续时长
+    pub fn duration(&self) -> TimeDelta {
+        self.end - self.start
+    }
+
+    /// 会话内的唯一目标文件数
+    pub fn unique_files(&self) -> usize {
+        self.targets.len()
+    }
+
+    /// 会话内的拦截率（百分比）
+    pub fn block_rate(&self) -> f64 {
+        if self.event_count > 0 {
+            self.blocked_count as f64 / self.event_count as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+
+    /// 扫描次数最多的前 `n` 个目标
+    pub fn top_targets(&self, n: usize) -> Vec<(&str, usize)> {
+        let mut targets: Vec<_> = self.targets.iter().map(|(f, c)| (f.as_str(), *c)).collect();
+        targets.sort_by(|a, b| b.1.cmp(&a.1).then(a.0.cmp(b.0)));
+        targets.truncate(n);
+        targets
+    }
+}
+
+/// 按空闲间隔将日志流切分为会话；日志可以乱序输入，无时间戳的条目会被忽略
+///
+/// 边读边聚类，只保存已形成的会话，内存占用与会话数及各会话内的不同目标数成正比，
+/// 与日志条目数无关。乱序到达的条目会并入所在的会话，必要时合并被它连接起来的两个会话，
+/// 结果与先按时间排序再聚类相同。
+#[derive(Debug)]
+pub struct SessionDetector {
+    gap: TimeDelta,
+    /// 按开始时间排列的会话，相邻会话之间的空闲间隔均超过 `gap`
+    sessions: Vec<ScanSession>,
+}
+
+impl SessionDetector {
+    pub fn new(gap: TimeDelta) -> Self {
+        SessionDetector {
+            gap,
+            sessions: Vec::new(),
+        }
+    }
+
+    /// 记录一条日志
+    pub fn record(&mut self, entry: &AceLogEntry) {
+        let Some(ts) = entry.timestamp else {
+            return;
+        };
+        let index = self.session_at(ts);
+        let session = &mut self.sessions[index];
+        session.event_count += 1;
+        if entry.is_blocked() {
+            session.blocked_count += 1;
+        }
+        if let Some(target) = &entry.target_file {
+            match session.targets.get_mut(target) {
+                Some(count) => *count += 1,
+                None => {
+                    session.targets.insert(target.clone(), 1);
+                }
+            }
+        }
+    }
+
+    /// 返回 `ts` 所属会话的下标：与前后会话的间隔不超过 `gap` 时并入（两边都不超过时合并两者），
+    /// 否则新建会话
+    fn session_at(&mut self, ts: NaiveDateTime) -> usize {
+        // 第一个开始时间晚于 `ts` 的会话；按时间顺序输入时总是末尾，即只需看最后一个会话
+        let next = self.sessions.partition_point(|s| s.start <= ts);
+        let joins_previous = next > 0 && ts - self.sessions[next - 1].end <= self.gap;
+        let joins_next = next < self.sessions.len() && self.sessions[next].start - ts <= self.gap;
+        match (joins_previous, joins_next) {
+            (true, true) => {
+                let later = self.sessions.remove(next);
+                self.sessions[next - 1].absorb(later);
+                next - 1
+            }
+            (true, false) => {
+                let session = &mut self.sessions[next - 1];
+                session.end = session.end.max(ts);
+                next - 1
+            }
+            (false, true) => {
+                self.sessions[next].start = ts;
+                next
+            }
+            (false, false) => {
+                self.sessions.insert(next, ScanSession::new(ts));
+                next
+            }
+        }
+    }
+
+    /// 返回全部会话（按开始时间排列）
+    pub fn finish(self) -> Vec<ScanSession> {
+        self.sessions
+    }
+}
+
+/// 对一组日志条目做会话切分
+pub fn detect_sessions<'a, I>(entries: I, gap: TimeDelta) -> Vec<ScanSession>
+where
+    I: IntoIterator<Item = &'a AceLogEntry>,
+{
+    let mut detector = SessionDetector::new(gap);
+    for entry in entries {
+        detector.record(entry);
+    }
+    detector.finish()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn entry(time: &str, target: &str, result: &str) -> AceLogEntry {
+        AceLogEntry {
+            timestamp: NaiveDateTime::parse_from_str(
+                &format!("2024-05-01 {}", time),
+                "%Y-%m-%d %H:%M:%S",
+            )
+            .ok(),
+            target_file: Some(target.to_string()),
+            result: Some(result.to_string()),
+            ..AceLogEntry::default()
+        }
+    }
+
+    fn spans(sessions: &[ScanSession]) -> Vec<(String, String, usize)> {
+        sessions
+            .iter()
+            .map(|s| {
+                (
+                    s.start.format("%H:%M:%S").to_string(),
+                    s.end.format("%H:%M:%S").to_string(),
+                    s.event_count,
+                )
+            })
+            .collect()
+    }
+
+    fn span(start: &str, end: &str, events: usize) -> (String, String, usize) {
+        (start.to_string(), end.to_string(), events)
+    }
+
+    #[test]
+    fn gap_equal_to_the_limit_stays_in_the_session() {
+        let entries = [
+            entry("10:00:00", "a", "已阻止"),
+            entry("10:10:00", "a", "已放行"),
+            entry("10:20:01", "b", "已阻止"),
+        ];
+        let sessions = detect_sessions(&entries, DEFAULT_SESSION_GAP);
+        assert_eq!(
+            spans(&sessions),
+            [
+                span("10:00:00", "10:10:00", 2),
+                span("10:20:01", "10:20:01", 1)
+            ]
+        );
+        assert_eq!(sessions[0].blocked_count, 1);
+        assert_eq!(sessions[0].targets["a"], 2);
+    }
+
+    #[test]
+    fn out_of_order_input_matches_sorted_input() {
+        let sorted = [
+            entry("09:00:00", "a", "已阻止"),
+            entry("09:05:00", "b", "已阻止"),
+            entry("09:30:00", "c", "已放行"),
+            entry("09:38:00", "a", "已阻止"),
+            entry("11:00:00", "d", "已阻止"),
+        ];
+        let shuffled = [4, 2, 0, 3, 1].map(|i| sorted[i].clone());
+        let expected = detect_sessions(&sorted, DEFAULT_SESSION_GAP);
+        let actual = detect_sessions(&shuffled, DEFAULT_SESSION_GAP);
+        assert_eq!(spans(&actual), spans(&expected));
+        assert_eq!(
+            spans(&actual),
+            [
+                span("09:00:00", "09:05:00", 2),
+                span("09:30:00", "09:38:00", 2),
+                span("11:00:00", "11:00:00", 1),
+            ]
+        );
+        for (a, e) in actual.iter().zip(&expected) {
+            assert_eq!(a.targets, e.targets);
+            assert_eq!(a.blocked_count, e.blocked_count);
+        }
+    }
+
+    #[test]
+    fn late_entry_bridges_two_sessions() {
+        let entries = [
+            entry("10:00:00", "a", "已阻止"),
+            entry("10:16:00", "a", "已阻止"),
+            entry("10:08:00", "b", "已放行"),
+        ];
+        let sessions = detect_sessions(&entries, DEFAULT_SESSION_GAP);
+        assert_eq!(spans(&sessions), [span("10:00:00", "10:16:00", 3)]);
+        assert_eq!(sessions[0].blocked_count, 2);
+        assert_eq!(sessions[0].targets["a"], 2);
+    }
+
+    #[test]
+    fn ignores_entries_without_timestamps() {
+        let entries = [AceLogEntry::default(), entry("10:00:00", "a", "已阻止")];
+        assert_eq!(
+            spans(&detect_sessions(&entries, DEFAULT_SESSION_GAP)),
+            [span("10:00:00", "10:00:00", 1)]
+        );
+    }
+}
@@ -0,0 +1,216 @@
+use std::collections::{BTreeMap, HashMap};
+
+use chrono::{NaiveDate, NaiveDateTime, Timelike};
+use serde::{Deserialize, Serialize};
+
+use crate::category::CategoryRules;
+use crate::entry::AceLogEntry;
+use crate::outcome::OperationOutcome;
+use crate::winpath::WinPath;
+
+/// ACE 扫盘行为汇总统计
+#[derive(Debug, Default, Clone, Serialize, Deserialize)]
+#[serde(default)]
+pub struct AceScanStats {
+    pub total_attempts: usize,
+    pub blocked_attempts: usize,
+    /// 多份输入日志相互重叠而未计入的重复条目数
+    pub duplicate_entries: usize,
+    pub unique_files: HashMap<String, usize>,
+    /// 操作结果 ID（见 [`OperationOutcome::id`]）的次数
+    pub outcomes: HashMap<String, usize>,
+    /// 扫描目标 → 操作结果 ID → 次数
+    pub file_outcomes: HashMap<String, HashMap<String, usize>>,
+    pub processes: HashMap<String, usize>,
+    /// 进程完整路径 → 启动参数 → 次数，区分同一程序的不同启动方式
+    pub process_instances: HashMap<String, HashMap<String, usize>>,
+    pub rules_triggered: HashMap<String, usize>,
+    /// 小写扩展名的扫描次数，无扩展名记为空字符串
+    pub file_extensions: HashMap<String, usize>,
+    /// 分类 ID 的扫描次数，显示名见 [`CategoryRules::label`]
+    pub target_categories: HashMap<String, usize>,
+    /// 操作类型 ID（见 [`OperationKind::id`](crate::OperationKind::id)）的次数
+    pub operation_types: HashMap<String, usize>,
+    /// 分类 ID → 操作类型 ID → 次数
+    pub category_operations: HashMap<String, HashMap<String, usize>>,
+    /// 进程名 → 操作类型 ID → 次数
+    pub process_operations: HashMap<String, HashMap<String, usize>>,
+    /// 分类 ID → 操作结果 ID → 次数
+    pub category_outcomes: HashMap<String, HashMap<String, usize>>,
+    /// 进程名 → 操作结果 ID → 次数
+    pub process_outcomes: HashMap<String, HashMap<String, usize>>,
+    /// 规则名 → 操作结果 ID → 次数
+    pub rule_outcomes: HashMap<String, HashMap<String, usize>>,
+    /// 进程名 → 分类 ID → 次数
+    pub process_categories: HashMap<String, HashMap<String, usize>>,
+    /// 进程名 → 小写扩展名 → 次数，无扩展名记为空字符串
+    pub process_extensions: HashMap<String, HashMap<String, usize>>,
+    pub time_distribution: BTreeMap<String, usize>,
+    /// 时段（同 `time_distribution` 的键）→ 操作结果 ID → 次数
+    pub hour_outcomes: BTreeMap<String, HashMap<String, usize>>,
+    /// 每日扫描次数
+    pub daily_totals: BTreeMap<NaiveDate, usize>,
+    /// 日期 × 小时（0-23）扫描次数矩阵
+    pub day_hour_matrix: BTreeMap<NaiveDate, [usize; 24]>,
+    /// 最早一条日志的时间
+    pub first_seen: Option<NaiveDateTime>,
+    /// 最晚一条日志的时间
+    pub last_seen: Option<NaiveDateTime>,
+}
+
+impl AceScanStats {
+    /// 由解析出的日志条目汇总统计
+    pub fn from_entries<I: IntoIterator<Item = AceLogEntry>>(entries: I) -> Self {
+        let mut stats = AceScanStats::default();
+        for entry in entries {
+            stats.record(&entry);
+        }
+        stats
+    }
+
+    /// 将单条日志计入统计（使用内置分类规则）
+    pub fn record(&mut self, entry: &AceLogEntry) {
+        self.record_with(entry, CategoryRules::builtin());
+    }
+
+    /// 将单条日志计入统计，扫描目标按 `rules` 分类
+    pub fn record_with(&mut self, entry: &AceLogEntry, rules: &CategoryRules) {
+        self.total_attempts += 1;
+
+        let outcome = entry.outcome();
+        *self.outcomes.entry(outcome.id().to_string()).or_insert(0) += 1;
+
+        let operation = entry.operation().map(|op| op.id());
+        if let Some(op) = operation {
+            *self.operation_types.entry(op.to_string()).or_insert(0) += 1;
+        }
+
+        let proc_name = entry
+            .process_path
+            .as_ref()
+            .map(|_| entry.process_name().unwrap_or("unknown"));
+        if let Some(process_path) = &entry.process_path {
+            let arguments = entry.arguments().unwrap_or_default();
+            count_pair(&mut self.process_instances, process_path, arguments);
+        }
+        if let Some(proc_name) = proc_name {
+            *self.processes.entry(proc_name.to_string()).or_insert(0) += 1;
+            count_pair(&mut self.process_outcomes, proc_name, outcome.id());
+            if let Some(op) = operation {
+                count_pair(&mut self.process_operations, proc_name, op);
+            }
+        }
+
+        if let Some(file_path) = &entry.target_file {
+            *self.unique_files.entry(file_path.clone()).or_insert(0) += 1;
+            count_pair(&mut self.file_outcomes, file_path, outcome.id());
+
+            let ext = WinPath::parse(file_path)
+                .extension()
+                .unwrap_or_default();
+            let category = rules.categorize(file_path);
+            if let Some(proc_name) = proc_name {
+                count_pair(&mut self.process_categories, proc_name, category);
+                count_pair(&mut self.process_extensions, proc_name, &ext);
+            }
+            *self.file_extensions.entry(ext).or_insert(0) += 1;
+
+            *self.target_categories.entry(category.to_string()).or_insert(0) += 1;
+            count_pair(&mut self.category_outcomes, category, outcome.id());
+            if let Some(op) = operation {
+                count_pair(&mut self.category_operations, category, op);
+            }
+        }
+
+        if let Some(rule) = &entry.rule_name {
+            *self.rules_triggered.entry(rule.clone()).or_insert(0) += 1;
+            count_pair(&mut self.rule_outcomes, rule, outcome.id());
+        }
+
+        if outcome.is_blocked() {
+            self.blocked_attempts += 1;
+        }
+
+        if let Some(ts) = entry.timestamp {
+            let hour = ts.hour();
+            let hour_key = format!("{:02}:00-{:02}:59", hour, hour);
+            *self
+                .hour_outcomes
+                .entry(hour_key.clone())
+                .or_default()
+                .entry(outcome.id().to_string())
+                .or_insert(0) += 1;
+            *self.time_distribution.entry(hour_key).or_insert(0) += 1;
+
+            let day = ts.date();
+            *self.daily_totals.entry(day).or_insert(0) += 1;
+            self.day_hour_matrix.entry(day).or_insert([0; 24])[hour as usize] += 1;
+
+            self.first_seen = Some(self.first_seen.map_or(ts, |t| t.min(ts)));
+            self.last_seen = Some(self.last_seen.map_or(ts, |t| t.max(ts)));
+        }
+    }
+
+    /// 按次数降序（相同时按路径、参数）排列的进程实例：`(进程路径, 启动参数, 次数)`
+    pub fn sorted_process_instances(&self) -> Vec<(&str, &str, usize)> {
+        let mut instances: Vec<_> = self
+            .process_instances
+            .iter()
+            .flat_map(|(path, args)| {
+                args.iter()
+                    .map(move |(arg, count)| (path.as_str(), arg.as_str(), *count))
+            })
+            .collect();
+        instances.sort_by(|a, b| b.2.cmp(&a.2).then(a.0.cmp(b.0)).then(a.1.cmp(b.1)));
+        instances
+    }
+
+    /// 扫描目标被阻止（含询问后阻止）的次数
+    pub fn blocked_count(&self, file: &str) -> usize {
+        self.file_outcomes
+            .get(file)
+            .map_or(0, |outcomes| count_where(outcomes, OperationOutcome::is_blocked))
+    }
+
+    /// 被放行（含询问后允许）过的扫描目标：`(路径, 放行次数, 扫描次数)`，按放行次数降序
+    pub fn allowed_targets(&self) -> Vec<(&str, usize, usize)> {
+        let mut targets: Vec<_> = self
+            .file_outcomes
+            .iter()
+            .map(|(file, outcomes)| {
+                let allowed = count_where(outcomes, OperationOutcome::is_allowed);
+                (file.as_str(), allowed, outcomes.values().sum::<usize>())
+            })
+            .filter(|(_, allowed, _)| *allowed > 0)
+            .collect();
+        targets.sort_by(|a, b| b.1.cmp(&a.1).then(b.2.cmp(&a.2)).then(a.0.cmp(b.0)));
+        targets
+    }
+
+    /// 拦截率（百分比）
+    pub fn block_rate(&self) -> f64 {
+        if self.total_attempts > 0 {
+            self.blocked_attempts as f64 / self.total_attempts as f64 * 100.0
+        } else {
+            0.0
+        }
+    }
+}
+
+/// 按结果 ID 计数的表中满足 `pred` 的结果次数之和
+fn count_where(outcomes: &HashMap<String, usize>, pred: fn(OperationOutcome) -> bool) -> usize {
+    outcomes
+        .iter()
+        .filter(|(id, _)| id.parse().is_ok_and(pred))
+        .map(|(_, count)| count)
+        .sum()
+}
+
+/// 二维计数表中 `row` 行 `column` 列加一
+fn count_pair(table: &mut HashMap<String, HashMap<String, usize>>, row: &str, column: &str) {
+    *table
+        .entry(row.to_string())
+        .or_default()
+        .entry(column.to_string())
+        .or_insert(0) += 1;
+}
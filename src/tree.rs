@@ -0,0 +1,179 @@
+use std::collections::{BTreeMap, HashMap};
+
+use crate::stats::AceScanStats;
+use crate::winpath::WinPath;
+
+/// 目录树中的一个节点（目录或文件）
+#[derive(Debug, Clone, Default)]
+pub struct DirNode {
+    /// 路径分量名，如 `System32`
+    pub name: String,
+    /// 以该路径本身为目标的扫描次数
+    pub self_count: usize,
+    /// 该节点及其全部子孙的扫描次数
+    pub subtree_count: usize,
+    /// 该节点及其全部子孙中被扫描过的不同路径数
+    pub unique_files: usize,
+    /// 该节点及其全部子孙被火绒阻止的扫描次数
+    pub blocked_count: usize,
+    pub children: BTreeMap<String, DirNode>,
+}
+
+impl DirNode {
+    fn named(name: &str) -> Self {
+        DirNode {
+            name: name.to_string(),
+            ..DirNode::default()
+        }
+    }
+
+    /// 按扫描次数降序排列的子节点
+    pub fn sorted_children(&self) -> Vec<&DirNode> {
+        let mut children: Vec<_> = self.children.values().collect();
+        children.sort_by(|a, b| b.subtree_count.cmp(&a.subtree_count).then(a.name.cmp(&b.name)));
+        children
+    }
+
+    /// 是否为目录（有子节点）
+    pub fn is_dir(&self) -> bool {
+        !self.children.is_empty()
+    }
+}
+
+/// 扫描目标按目录层级折叠而成的前缀树
+#[derive(Debug, Clone, Default)]
+pub struct DirTree {
+    pub root: DirNode,
+}
+
+impl DirTree {
+    /// 由「路径 → 扫描次数」构建目录树
+    pub fn from_files(files: &HashMap<String, usize>) -> Self {
+        let mut tree = DirTree::default();
+        for (path, count) in files {
+            tree.insert(path, *count, 0);
+        }
+        tree
+    }
+
+    /// 由统计中的扫描目标及其拦截次数构建目录树
+    pub fn from_stats(stats: &AceScanStats) -> Self {
+        let mut tree = DirTree::default();
+        for (path, count) in &stats.unique_files {
+            tree.insert(path, *count, stats.blocked_count(path));
+        }
+        tree
+    }
+
+    /// 计入一条路径的扫描次数与其中被阻止的次数
+    ///
+    /// 顶层节点为 [`WinPath::root`]（盘符、`\\server\share` 或 `\Device\HarddiskVolumeN`），
+    /// 没有根的相对路径从第一级目录开始。
+    pub fn insert(&mut self, path: &str, count: usize, blocked: usize) {
+        let path = WinPath::parse(path);
+        let parts: Vec<_> = path
+            .root
+            .iter()
+            .chain(&path.components)
+            .map(String::as_str)
+            .collect();
+        let is_new = self.get(&parts).is_none_or(|n| n.self_count == 0);
+
+        let mut node = &mut self.root;
+        node.subtree_count += count;
+        node.blocked_count += blocked;
+        node.unique_files += usize::from(is_new);
+        for part in parts {
+            node = node
+                .children
+                .entry(part.to_string())
+                .or_insert_with(|| DirNode::named(part));
+            node.subtree_count += count;
+            node.blocked_count += blocked;
+            node.unique_files += usize::from(is_new);
+        }
+        node.self_count += count;
+    }
+
+    fn get(&self, parts: &[&str]) -> Option<&DirNode> {
+        parts
+            .iter()
+            .try_fold(&self.root, |node, part| node.children.get(*part))
+    }
+
+    /// 扫描最集中的目录：从根开始逐层只展开扫描次数最多的 `breadth` 个子目录，
+    /// 最深到第 `max_depth` 层（盘符为第 1 层），按树的先序返回完整路径与节点
+    pub fn hot_dirs(&self, max_depth: usize, breadth: usize) -> Vec<(String, &DirNode)> {
+        let mut dirs = Vec::new();
+        collect_hot_dirs(&self.root, "", 1, max_depth, breadth, &mut dirs);
+        dirs
+    }
+}
+
+fn collect_hot_dirs<'a>(
+    node: &'a DirNode,
+    prefix: &str,
+    depth: usize,
+    max_depth: usize,
+    breadth: usize,
+    out: &mut Vec<(String, &'a DirNode)>,
+) {
+    if depth > max_depth {
+        return;
+    }
+    for child in node.sorted_children().into_iter().filter(|c| c.is_dir()).take(breadth) {
+        let path = if prefix.is_empty() {
+            child.name.clone()
+        } else {
+            format!("{}\\{}", prefix, child.name)
+        };
+        out.push((path.clone(), child));
+        collect_hot_dirs(child, &path, depth + 1, max_depth, breadth, out);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn top_level(tree: &DirTree) -> Vec<&str> {
+        tree.root.children.keys().map(String::as_str).collect()
+    }
+
+    #[test]
+    fn keeps_device_and_unc_roots_whole() {
+        let mut tree = DirTree::default();
+        tree.insert(r"\Device\HarddiskVolume3\Windows\a.dll", 2, 1);
+        tree.insert(r"\\server\share\games\b.pak", 1, 0);
+        tree.insert(r"C:\Windows\c.sys", 1, 1);
+        assert_eq!(
+            top_level(&tree),
+            ["C:", r"\Device\HarddiskVolume3", r"\\server\share"]
+        );
+        let volume = &tree.root.children[r"\Device\HarddiskVolume3"];
+        assert_eq!(volume.subtree_count, 2);
+        assert_eq!(volume.blocked_count, 1);
+        assert!(volume.children.contains_key("Windows"));
+    }
+
+    #[test]
+    fn counts_unique_files_once_per_path() {
+        let mut tree = DirTree::default();
+        tree.insert(r"C:\Windows\a.dll", 3, 0);
+        tree.insert(r"C:\Windows\a.dll", 2, 0);
+        tree.insert(r"C:\Windows\b.dll", 1, 0);
+        let windows = &tree.root.children["C:"].children["Windows"];
+        assert_eq!(windows.subtree_count, 6);
+        assert_eq!(windows.unique_files, 2);
+        assert_eq!(windows.children["a.dll"].self_count, 5);
+    }
+
+    #[test]
+    fn hot_dirs_expand_busiest_directories_in_pre_order() {
+        let mut tree = DirTree::default();
+        tree.insert(r"C:\Windows\System32\a.dll", 5, 0);
+        tree.insert(r"C:\Users\u\b.txt", 1, 0);
+        let dirs: Vec<_> = tree.hot_dirs(2, 1).into_iter().map(|(path, _)| path).collect();
+        assert_eq!(dirs, ["C:", r"C:\Windows"]);
+    }
+}
@@ -0,0 +1,309 @@
+/// 由路径结构识别出的 Windows 常用目录
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KnownFolder {
+    /// `<盘符>\Windows`
+    Windows,
+    /// `<盘符>\Windows\System32`
+    System32,
+    /// `<盘符>\Windows\SysWOW64`
+    SysWow64,
+    /// `System32\drivers` 或 `SysWOW64\drivers`
+    Drivers,
+    /// `<盘符>\Windows\WinSxS`
+    WinSxS,
+    /// `<盘符>\Program Files`
+    ProgramFiles,
+    /// `<盘符>\Program Files (x86)`
+    ProgramFilesX86,
+    /// `<盘符>\ProgramData`
+    ProgramData,
+    /// `<盘符>\Users\<用户名>`
+    UserProfile,
+    /// `<盘符>\Users\<用户名>\AppData`
+    AppData,
+}
+
+impl KnownFolder {
+    /// 规则文件中使用的名称
+    pub fn name(self) -> &'static str {
+        match self {
+            KnownFolder::Windows => "windows",
+            KnownFolder::System32 => "system32",
+            KnownFolder::SysWow64 => "syswow64",
+            KnownFolder::Drivers => "drivers",
+            KnownFolder::WinSxS => "winsxs",
+            KnownFolder::ProgramFiles => "program_files",
+            KnownFolder::ProgramFilesX86 => "program_files_x86",
+            KnownFolder::ProgramData => "program_data",
+            KnownFolder::UserProfile => "user_profile",
+            KnownFolder::AppData => "app_data",
+        }
+    }
+
+    /// 全部已知目录
+    pub const ALL: [KnownFolder; 10] = [
+        KnownFolder::Windows,
+        KnownFolder::System32,
+        KnownFolder::SysWow64,
+        KnownFolder::Drivers,
+        KnownFolder::WinSxS,
+        KnownFolder::ProgramFiles,
+        KnownFolder::ProgramFilesX86,
+        KnownFolder::ProgramData,
+        KnownFolder::UserProfile,
+        KnownFolder::AppData,
+    ];
+
+    /// 按规则文件中的名称查找（不区分大小写）
+    pub fn from_name(name: &str) -> Option<Self> {
+        Self::ALL.into_iter().find(|f| f.name().eq_ignore_ascii_case(name))
+    }
+}
+
+/// 拆分为各个分量的 Windows 路径
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct WinPath {
+    /// 根：盘符（`C:`）、UNC 共享（`\\server\share`）或设备卷（`\Device\HarddiskVolume3`）
+    pub root: Option<String>,
+    /// 根之后的各级目录名与文件名
+    pub components: Vec<String>,
+}
+
+impl WinPath {
+    /// 解析路径，`\` 与 `/` 均视为分隔符，并去掉 `\\?\`、`\??\` 等前缀
+    pub fn parse(path: &str) -> Self {
+        let path = path.trim();
+        let path = ["\\\\?\\UNC\\", "\\\\?\\", "\\??\\", "\\\\.\\"]
+            .iter()
+            .find_map(|prefix| {
+                path.strip_prefix(prefix).map(|rest| {
+                    if *prefix == "\\\\?\\UNC\\" {
+                        format!("\\\\{}", rest)
+                    } else {
+                        rest.to_string()
+                    }
+                })
+            })
+            .unwrap_or_else(|| path.to_string());
+
+        let is_unc = path.starts_with("\\\\") || path.starts_with("//");
+        let mut parts = path
+            .split(['\\', '/'])
+            .filter(|p| !p.is_empty() && *p != ".")
+            .map(str::to_string)
+            .peekable();
+
+        let root = if is_unc {
+            let server = parts.next();
+            let share = parts.next();
+            server.map(|s| match share {
+                Some(share) => format!("\\\\{}\\{}", s, share),
+                None => format!("\\\\{}", s),
+            })
+        } else if parts.peek().is_some_and(|p| is_drive(p)) {
+            parts.next().map(|d| d.to_uppercase())
+        } else if path.starts_with(['\\', '/'])
+            && parts.peek().is_some_and(|p| p.eq_ignore_ascii_case("device"))
+        {
+            let device = parts.next().unwrap_or_default();
+            parts.next().map(|volume| format!("\\{}\\{}", device, volume))
+        } else {
+            None
+        };
+
+        WinPath {
+            root,
+            components: parts.collect(),
+        }
+    }
+
+    /// 文件名（最后一个分量）
+    pub fn file_name(&self) -> Option<&str> {
+        self.components.last().map(String::as_str)
+    }
+
+    /// 文件名之前的各级目录
+    pub fn dirs(&self) -> &[String] {
+        match self.components.split_last() {
+            Some((_, dirs)) => dirs,
+            None => &[],
+        }
+    }
+
+    /// 小写扩展名；无扩展名或以 `.` 开头的隐藏文件名返回 `None`
+    pub fn extension(&self) -> Option<String> {
+        let name = self.file_name()?;
+        match name.rsplit_once('.') {
+            Some((stem, ext)) if !stem.is_empty() && !ext.is_empty() => Some(ext.to_lowercase()),
+            _ => None,
+        }
+    }
+
+    /// 是否有某个分量与 `name` 完全相同（不区分大小写）
+    pub fn has_component(&self, name: &str) -> bool {
+        self.components.iter().any(|c| eq_ignore_case(c, name))
+    }
+
+    /// 是否有某个分量以 `prefix` 开头（不区分大小写）
+    pub fn has_component_prefix(&self, prefix: &str) -> bool {
+        let prefix = prefix.to_lowercase();
+        self.components.iter().any(|c| c.to_lowercase().starts_with(&prefix))
+    }
+
+    /// 分量中是否连续出现 `sequence`（如 `["system32", "drivers"]`）
+    pub fn has_sequence(&self, sequence: &[String]) -> bool {
+        !sequence.is_empty()
+            && self
+                .components
+                .windows(sequence.len())
+                .any(|w| w.iter().zip(sequence).all(|(a, b)| eq_ignore_case(a, b)))
+    }
+
+    /// 路径位于的全部已知目录，由外到内排列
+    pub fn known_folders(&self) -> Vec<KnownFolder> {
+        let dirs: Vec<String> = self.dirs().iter().map(|d| d.to_lowercase()).collect();
+        let at = |i: usize, name: &str| dirs.get(i).is_some_and(|d| d == name);
+        let mut folders = Vec::new();
+
+        if self.root.is_none() {
+            return folders;
+        }
+        if at(0, "windows") {
+            folders.push(KnownFolder::Windows);
+            let system = if at(1, "system32") {
+                Some(KnownFolder::System32)
+            } else if at(1, "syswow64") {
+                Some(KnownFolder::SysWow64)
+            } else if at(1, "winsxs") {
+                folders.push(KnownFolder::WinSxS);
+                None
+            } else {
+                None
+            };
+            if let Some(system) = system {
+                folders.push(system);
+                if at(2, "drivers") {
+                    folders.push(KnownFolder::Drivers);
+                }
+            }
+        } else if at(0, "program files") {
+            folders.push(KnownFolder::ProgramFiles);
+        } else if at(0, "program files (x86)") {
+            folders.push(KnownFolder::ProgramFilesX86);
+        } else if at(0, "programdata") {
+            folders.push(KnownFolder::ProgramData);
+        } else if at(0, "users") && dirs.len() > 1 {
+            folders.push(KnownFolder::UserProfile);
+            if at(2, "appdata") {
+                folders.push(KnownFolder::AppData);
+            }
+        }
+        folders
+    }
+
+    /// 是否以 `prefix` 路径开头（按整段分量比较，`C:\Program Files` 不匹配 `C:\Program Files (x86)`）
+    pub fn starts_with(&self, prefix: &WinPath) -> bool {
+        let root_matches = match (&self.root, &prefix.root) {
+            (_, None) => true,
+            (Some(a), Some(b)) => eq_ignore_case(a, b),
+            (None, Some(_)) => false,
+        };
+        root_matches
+            && prefix.components.len() <= self.components.len()
+            && self
+                .components
+                .iter()
+                .zip(&prefix.components)
+                .all(|(a, b)| eq_ignore_case(a, b))
+    }
+}
+
+fn is_drive(part: &str) -> bool {
+    let bytes = part.as_bytes();
+    bytes.len() == 2 && bytes[0].is_ascii_alphabetic() && bytes[1] == b':'
+}
+
+fn eq_ignore_case(a: &str, b: &str) -> bool {
+    a.eq_ignore_ascii_case(b) || a.to_lowercase() == b.to_lowercase()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parts(path: &str) -> (Option<String>, Vec<String>) {
+        let path = WinPath::parse(path);
+        (path.root, path.components)
+    }
+
+    fn owned(parts: &[&str]) -> Vec<String> {
+        parts.iter().map(|p| p.to_string()).collect()
+    }
+
+    #[test]
+    fn parses_drive_unc_and_device_roots() {
+        assert_eq!(
+            parts(r"c:\Windows\System32\ntdll.dll"),
+            (Some("C:".into()), owned(&["Windows", "System32", "ntdll.dll"]))
+        );
+        assert_eq!(
+            parts(r"\\nas\games\Delta Force\df.exe"),
+            (Some(r"\\nas\games".into()), owned(&["Delta Force", "df.exe"]))
+        );
+        assert_eq!(
+            parts(r"\Device\HarddiskVolume3\Users\a.txt"),
+            (Some(r"\Device\HarddiskVolume3".into()), owned(&["Users", "a.txt"]))
+        );
+        assert_eq!(parts(r"Windows\.\a.txt"), (None, owned(&["Windows", "a.txt"])));
+    }
+
+    #[test]
+    fn strips_extended_length_prefixes() {
+        assert_eq!(WinPath::parse(r"\\?\C:\Windows\a.dll"), WinPath::parse(r"C:\Windows\a.dll"));
+        assert_eq!(WinPath::parse(r"\??\C:\Windows\a.dll"), WinPath::parse(r"C:\Windows\a.dll"));
+        assert_eq!(
+            WinPath::parse(r"\\?\UNC\nas\games\df.exe"),
+            WinPath::parse(r"\\nas\games\df.exe")
+        );
+        assert_eq!(WinPath::parse("C:/Windows/a.dll"), WinPath::parse(r"C:\Windows\a.dll"));
+    }
+
+    #[test]
+    fn extension_ignores_dotfiles_and_trailing_dots() {
+        assert_eq!(WinPath::parse(r"C:\a\Game.PAK").extension().as_deref(), Some("pak"));
+        assert_eq!(WinPath::parse(r"C:\a\.gitconfig").extension(), None);
+        assert_eq!(WinPath::parse(r"C:\a\README").extension(), None);
+        assert_eq!(WinPath::parse(r"C:\a\name.").extension(), None);
+    }
+
+    #[test]
+    fn recognizes_known_folders() {
+        let folders = |path: &str| WinPath::parse(path).known_folders();
+        assert_eq!(
+            folders(r"C:\Windows\System32\drivers\ACE-BASE.sys"),
+            [KnownFolder::Windows, KnownFolder::System32, KnownFolder::Drivers]
+        );
+        assert_eq!(
+            folders(r"C:\Windows\WinSxS\amd64\a.dll"),
+            [KnownFolder::Windows, KnownFolder::WinSxS]
+        );
+        assert_eq!(
+            folders(r"D:\Program Files (x86)\Steam\steam.exe"),
+            [KnownFolder::ProgramFilesX86]
+        );
+        assert_eq!(
+            folders(r"C:\Users\alice\AppData\Local\a.db"),
+            [KnownFolder::UserProfile, KnownFolder::AppData]
+        );
+        assert_eq!(folders(r"C:\Users\desktop.ini"), []);
+        assert_eq!(folders(r"Windows\System32\a.dll"), []);
+    }
+
+    #[test]
+    fn starts_with_compares_whole_components() {
+        let path = WinPath::parse(r"C:\Program Files (x86)\a.exe");
+        assert!(!path.starts_with(&WinPath::parse(r"C:\Program Files")));
+        assert!(path.starts_with(&WinPath::parse(r"c:\program files (x86)")));
+        assert!(path.starts_with(&WinPath::parse(r"Program Files (x86)")));
+    }
+}